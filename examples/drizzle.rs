@@ -0,0 +1,10 @@
+mod utils;
+
+use std::error::Error;
+
+use tui_rain::Rain;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    utils::render_rain(Box::new(Rain::new_drizzle)).await
+}
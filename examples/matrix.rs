@@ -6,5 +6,5 @@ use tui_rain::Rain;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    utils::render_rain(Box::new(|elapsed| Rain::new_matrix(elapsed))).await
+    utils::render_rain(Box::new(Rain::new_matrix)).await
 }
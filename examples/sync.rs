@@ -0,0 +1,44 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crossterm::event::{self, Event, KeyCode};
+use ratatui::Frame;
+use tui_rain::Rain;
+
+fn main() {
+    let mut terminal = ratatui::init();
+    terminal.clear().unwrap();
+
+    let tick_duration = Duration::from_secs_f64(1.0 / 60.0);
+
+    let start_time = Instant::now();
+
+    while start_time.elapsed() < Duration::from_secs(10) {
+        let tick_start = Instant::now();
+
+        terminal
+            .draw(|frame| render(frame, start_time.elapsed()))
+            .unwrap();
+
+        // Quit early if there's a 'q' key press, without blocking on input.
+        if event::poll(Duration::ZERO).unwrap() {
+            if let Event::Key(key_event) = event::read().unwrap() {
+                if key_event.code == KeyCode::Char('q') {
+                    break;
+                }
+            }
+        }
+
+        // Sleep off the rest of the tick.
+        thread::sleep(tick_duration.saturating_sub(tick_start.elapsed()));
+    }
+
+    ratatui::restore();
+}
+
+fn render(frame: &mut Frame, elapsed: Duration) {
+    let rain = Rain::new_matrix(elapsed);
+    frame.render_widget(rain, frame.area());
+}
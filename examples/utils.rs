@@ -4,7 +4,7 @@ use crossterm::event::{Event, EventStream, KeyCode};
 use futures::{FutureExt, StreamExt};
 use ratatui::{style::Stylize, DefaultTerminal, Frame};
 use tokio::time;
-use tui_rain::Rain;
+use tui_rain::{Rain, RainState};
 
 /// How much to smooth the FPS tracking.
 ///
@@ -42,6 +42,9 @@ async fn main_loop(
     let mut last_tick = time::Instant::now().checked_sub(tick_duration).unwrap();
     let mut fps: f64 = framerate;
 
+    // Initialize the rain state so the animation can be paused.
+    let mut rain_state = RainState::default();
+
     loop {
         // Wait for next tick or term signal
         tokio::select! {
@@ -52,7 +55,9 @@ async fn main_loop(
                 fps = fps.min(1e4) * FPS_SMOOTHING + (1.0 - FPS_SMOOTHING) / elapsed.as_secs_f64();
 
                 // Render
-                terminal.draw(|frame| render(&rain, frame, start_time.elapsed(), fps, show_fps))?;
+                terminal.draw(|frame| {
+                    render(&rain, &mut rain_state, frame, start_time.elapsed(), fps, show_fps)
+                })?;
             },
 
             event = reader.next().fuse() => match event {
@@ -64,6 +69,14 @@ async fn main_loop(
                 Some(Ok(Event::Key(key_event))) if key_event.code == KeyCode::Char('f') => {
                     show_fps = !show_fps
                 },
+                // Pause / resume the rain if it's a 'p' key press
+                Some(Ok(Event::Key(key_event))) if key_event.code == KeyCode::Char('p') => {
+                    if rain_state.is_paused() {
+                        rain_state.resume()
+                    } else {
+                        rain_state.pause()
+                    }
+                },
                 _ => {},
             },
         }
@@ -71,14 +84,15 @@ async fn main_loop(
 }
 
 fn render(
-    rain: &Box<dyn Fn(time::Duration) -> Rain>,
+    rain: &dyn Fn(time::Duration) -> Rain,
+    rain_state: &mut RainState,
     frame: &mut Frame,
     elapsed: time::Duration,
     fps: f64,
     show_fps: bool,
 ) {
     // Render the rain
-    frame.render_widget(rain(elapsed), frame.area());
+    frame.render_stateful_widget(rain(elapsed), frame.area(), rain_state);
 
     // Render the FPS tracker
     if show_fps {
@@ -0,0 +1,121 @@
+//! Encoding of buffer diffs as ANSI escape sequences, e.g. to stream over a network.
+
+use std::io::Write;
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+
+/// Encode the cells that changed between two buffers as a stream of ANSI escape sequences.
+///
+/// Writing the bytes to a terminal showing `previous` updates it to show `next`. Only
+/// changed cells are written, so this keeps the bandwidth low when streaming frames:
+///
+/// ```
+/// use ratatui::{buffer::Buffer, layout::Rect};
+/// use tui_rain::encode_ansi_diff;
+///
+/// let area = Rect::new(0, 0, 10, 2);
+/// let previous = Buffer::empty(area);
+/// let mut next = Buffer::empty(area);
+/// next[(3, 1)].set_char('x');
+///
+/// assert_eq!(encode_ansi_diff(&previous, &next), b"\x1b[2;4H\x1b[0mx\x1b[0m");
+/// ```
+///
+/// These escape sequences are used:
+///
+/// - `ESC [ <row> ; <column> H` moves the cursor, with 1-based coordinates relative to
+///   the buffers' origin. This is skipped between adjacent ASCII cells.
+/// - `ESC [ 0 ; <attributes> m` sets the style when it changes, after resetting it. The
+///   attributes are the standard SGR codes for each modifier (`1` bold, `2` dim, `3`
+///   italic, `4` underlined, `5` and `6` blinking, `7` reversed, `8` hidden, `9` crossed
+///   out), followed by the foreground and background colors. Named colors use `30`-`37`
+///   and `90`-`97` (`40`-`47` and `100`-`107` for backgrounds), indexed colors use
+///   `38;5;<n>`, and RGB colors use `38;2;<r>;<g>;<b>` (`48` for backgrounds).
+/// - Each changed cell's symbol, as UTF-8.
+/// - `ESC [ 0 m` resets the style at the end, if anything was written.
+///
+/// # Panics
+///
+/// Panics if the buffers have different areas.
+pub fn encode_ansi_diff(previous: &Buffer, next: &Buffer) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut cursor: Option<(u16, u16)> = None;
+    let mut style: Option<(Color, Color, Modifier)> = None;
+
+    for (x, y, cell) in previous.diff(next) {
+        // The cursor only advances predictably after single-column characters.
+        if cursor != Some((x, y)) {
+            let (row, column) = (y - next.area.y + 1, x - next.area.x + 1);
+            write!(bytes, "\x1b[{row};{column}H").unwrap();
+        }
+        cursor = cell.symbol().is_ascii().then_some((x + 1, y));
+
+        let cell_style = (cell.fg, cell.bg, cell.modifier);
+        if style != Some(cell_style) {
+            write_sgr(&mut bytes, cell);
+            style = Some(cell_style);
+        }
+
+        bytes.extend_from_slice(cell.symbol().as_bytes());
+    }
+
+    if style.is_some() {
+        bytes.extend_from_slice(b"\x1b[0m");
+    }
+    bytes
+}
+
+/// Write the SGR sequence setting exactly the style of a cell.
+fn write_sgr(bytes: &mut Vec<u8>, cell: &Cell) {
+    bytes.extend_from_slice(b"\x1b[0");
+    let modifiers = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::RAPID_BLINK, 6),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+    for (modifier, code) in modifiers {
+        if cell.modifier.contains(modifier) {
+            write!(bytes, ";{code}").unwrap();
+        }
+    }
+    write_color(bytes, cell.fg, 30);
+    write_color(bytes, cell.bg, 40);
+    bytes.push(b'm');
+}
+
+/// Write the SGR attributes for a color, given the base code for foreground or background.
+fn write_color(bytes: &mut Vec<u8>, color: Color, base: u8) {
+    let named = |offset: u8| base + offset;
+    let bright = |offset: u8| base + 60 + offset;
+    match color {
+        Color::Reset => return,
+        Color::Black => write!(bytes, ";{}", named(0)),
+        Color::Red => write!(bytes, ";{}", named(1)),
+        Color::Green => write!(bytes, ";{}", named(2)),
+        Color::Yellow => write!(bytes, ";{}", named(3)),
+        Color::Blue => write!(bytes, ";{}", named(4)),
+        Color::Magenta => write!(bytes, ";{}", named(5)),
+        Color::Cyan => write!(bytes, ";{}", named(6)),
+        Color::Gray => write!(bytes, ";{}", named(7)),
+        Color::DarkGray => write!(bytes, ";{}", bright(0)),
+        Color::LightRed => write!(bytes, ";{}", bright(1)),
+        Color::LightGreen => write!(bytes, ";{}", bright(2)),
+        Color::LightYellow => write!(bytes, ";{}", bright(3)),
+        Color::LightBlue => write!(bytes, ";{}", bright(4)),
+        Color::LightMagenta => write!(bytes, ";{}", bright(5)),
+        Color::LightCyan => write!(bytes, ";{}", bright(6)),
+        Color::White => write!(bytes, ";{}", bright(7)),
+        Color::Indexed(i) => write!(bytes, ";{};5;{i}", base + 8),
+        Color::Rgb(r, g, b) => write!(bytes, ";{};2;{r};{g};{b}", base + 8),
+    }
+    .unwrap();
+}
@@ -1,18 +1,41 @@
 #![doc = include_str!("../README.md")]
 
-use std::{cmp::Ordering, time::Duration};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    fmt::{self, Debug, Formatter},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "log")]
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, PoisonError},
+};
 
-use rand::{RngCore, SeedableRng};
-use rand_pcg::Pcg64Mcg;
+use rand::RngCore;
 use ratatui::{
-    buffer::Buffer,
+    buffer::{Buffer, Cell},
     layout::Rect,
-    style::{Color, Style, Stylize},
-    widgets::Widget,
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{StatefulWidget, Widget},
 };
 
+mod ansi;
+mod rng;
+
+pub use ansi::encode_ansi_diff;
+use rng::RainRng;
+pub use rng::RngKind;
+
 /// A configuration for the density of the rain effect.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+#[derive(Copy, Clone, Debug)]
 pub enum RainDensity {
     /// An absolute target number of drops to have in the frame.
     Absolute { num_drops: usize },
@@ -30,20 +53,176 @@ pub enum RainDensity {
 
     /// A sparse rain. Equivalent to `Relative { sparseness: 100 }`.
     Sparse,
+
+    /// An ultra-sparse drizzle. Equivalent to `Relative { sparseness: 400 }`.
+    Drizzle,
+
+    /// Compute the number of drops to cover a fraction of the frame's pixels on average.
+    ///
+    /// Each of the `n` drops covers about `L` pixels on average, where `L` is the mean
+    /// drop length from the speed and tail lifespan. Drops land independently, so they
+    /// cover about `1 - exp(-n * L / area)` of the frame, which is solved for `n`. Since
+    /// drops are randomly distributed, the actual coverage of each frame varies.
+    TargetCoverage { fraction: f64 },
 }
 
 impl RainDensity {
-    /// Get the absolute number of drops given an area.
-    fn num_drops(&self, area: Rect) -> usize {
+    /// Get the absolute number of drops given an area and the mean length of a drop.
+    fn num_drops(&self, area: Rect, drop_len: f64) -> usize {
         match self {
             RainDensity::Absolute { num_drops } => *num_drops,
             RainDensity::Relative { sparseness } if *sparseness == 0 => 0,
-            RainDensity::Relative { sparseness } => {
-                (area.width * area.height) as usize / *sparseness
+            RainDensity::Relative { sparseness } => area.area() as usize / *sparseness,
+            RainDensity::Dense => {
+                RainDensity::Relative { sparseness: 20 }.num_drops(area, drop_len)
+            }
+            RainDensity::Normal => {
+                RainDensity::Relative { sparseness: 50 }.num_drops(area, drop_len)
+            }
+            RainDensity::Sparse => {
+                RainDensity::Relative { sparseness: 100 }.num_drops(area, drop_len)
             }
-            RainDensity::Dense => RainDensity::Relative { sparseness: 20 }.num_drops(area),
-            RainDensity::Normal => RainDensity::Relative { sparseness: 50 }.num_drops(area),
-            RainDensity::Sparse => RainDensity::Relative { sparseness: 100 }.num_drops(area),
+            RainDensity::Drizzle => {
+                RainDensity::Relative { sparseness: 400 }.num_drops(area, drop_len)
+            }
+            // Full coverage would take infinitely many drops, so cap at one per pixel.
+            RainDensity::TargetCoverage { fraction } => {
+                let pixels = area.area() as f64;
+                let num_drops = -(1.0 - fraction.min(1.0)).ln() * pixels / drop_len.max(1.0);
+                num_drops.min(pixels).round() as usize
+            }
+        }
+    }
+
+    /// Sort key for comparing densities, with fractions compared by their total order.
+    fn key(&self) -> (u8, usize, f64) {
+        match *self {
+            RainDensity::Absolute { num_drops } => (0, num_drops, 0.0),
+            RainDensity::Relative { sparseness } => (1, sparseness, 0.0),
+            RainDensity::Dense => (2, 0, 0.0),
+            RainDensity::Normal => (3, 0, 0.0),
+            RainDensity::Sparse => (4, 0, 0.0),
+            RainDensity::Drizzle => (5, 0, 0.0),
+            RainDensity::TargetCoverage { fraction } => (6, 0, fraction),
+        }
+    }
+
+    /// Get the number of drops per pixel of a relative density, or `None` if not relative.
+    fn drops_per_pixel(&self) -> Option<f64> {
+        match self {
+            RainDensity::Absolute { .. } | RainDensity::TargetCoverage { .. } => None,
+            RainDensity::Relative { sparseness } if *sparseness == 0 => Some(0.0),
+            RainDensity::Relative { sparseness } => Some(1.0 / *sparseness as f64),
+            RainDensity::Dense => Some(1.0 / 20.0),
+            RainDensity::Normal => Some(1.0 / 50.0),
+            RainDensity::Sparse => Some(1.0 / 100.0),
+            RainDensity::Drizzle => Some(1.0 / 400.0),
+        }
+    }
+}
+
+impl PartialEq for RainDensity {
+    fn eq(&self, other: &RainDensity) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RainDensity {}
+
+impl PartialOrd for RainDensity {
+    fn partial_cmp(&self, other: &RainDensity) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RainDensity {
+    fn cmp(&self, other: &RainDensity) -> Ordering {
+        let (a, b) = (self.key(), other.key());
+        a.0.cmp(&b.0).then(a.1.cmp(&b.1)).then(a.2.total_cmp(&b.2))
+    }
+}
+
+impl Hash for RainDensity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let (variant, value, fraction) = self.key();
+        (variant, value, fraction.to_bits()).hash(state);
+    }
+}
+
+/// The names of densities, for parsing and formatting.
+///
+/// Parsing a density accepts these names, `absolute:<num drops>`, `relative:<sparseness>`,
+/// or `coverage:<fraction>`, ignoring case. Formatting a density gives the same names, so
+/// densities round-trip through strings, e.g. in config files:
+///
+/// ```
+/// use tui_rain::RainDensity;
+///
+/// let density: RainDensity = "absolute:120".parse().unwrap();
+/// assert_eq!(density, RainDensity::Absolute { num_drops: 120 });
+///
+/// for density in [
+///     RainDensity::Absolute { num_drops: 120 },
+///     RainDensity::Relative { sparseness: 30 },
+///     RainDensity::Dense,
+///     RainDensity::Normal,
+///     RainDensity::Sparse,
+///     RainDensity::Drizzle,
+///     RainDensity::TargetCoverage { fraction: 0.3 },
+/// ] {
+///     assert_eq!(density.to_string().parse::<RainDensity>(), Ok(density));
+/// }
+///
+/// assert!("heavy".parse::<RainDensity>().is_err());
+/// ```
+impl FromStr for RainDensity {
+    type Err = ParsePresetError;
+
+    fn from_str(s: &str) -> Result<RainDensity, ParsePresetError> {
+        let error = || ParsePresetError {
+            input: s.to_string(),
+            expected: "dense, normal, sparse, drizzle, absolute:<num drops>, \
+                       relative:<sparseness>, or coverage:<fraction>",
+        };
+        let value = |prefix: &str| -> Option<&str> {
+            let (name, value) = s.split_once(':')?;
+            name.eq_ignore_ascii_case(prefix).then(|| value.trim())
+        };
+        if let Some(num_drops) = value("absolute") {
+            return Ok(RainDensity::Absolute {
+                num_drops: num_drops.parse().map_err(|_| error())?,
+            });
+        }
+        if let Some(sparseness) = value("relative") {
+            return Ok(RainDensity::Relative {
+                sparseness: sparseness.parse().map_err(|_| error())?,
+            });
+        }
+        if let Some(fraction) = value("coverage") {
+            return Ok(RainDensity::TargetCoverage {
+                fraction: fraction.parse().map_err(|_| error())?,
+            });
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "dense" => Ok(RainDensity::Dense),
+            "normal" => Ok(RainDensity::Normal),
+            "sparse" => Ok(RainDensity::Sparse),
+            "drizzle" => Ok(RainDensity::Drizzle),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for RainDensity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RainDensity::Absolute { num_drops } => write!(f, "absolute:{num_drops}"),
+            RainDensity::Relative { sparseness } => write!(f, "relative:{sparseness}"),
+            RainDensity::Dense => f.write_str("dense"),
+            RainDensity::Normal => f.write_str("normal"),
+            RainDensity::Sparse => f.write_str("sparse"),
+            RainDensity::Drizzle => f.write_str("drizzle"),
+            RainDensity::TargetCoverage { fraction } => write!(f, "coverage:{fraction}"),
         }
     }
 }
@@ -51,9 +230,18 @@ impl RainDensity {
 /// The speed of the rain.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
 pub enum RainSpeed {
-    /// An absolute target speed in pixels / second.
+    /// An absolute target speed in rows / second. Equivalent to `RowsPerSecond`.
     Absolute { speed: f64 },
 
+    /// A target speed in rows / second, i.e. cell heights per second.
+    RowsPerSecond { speed: f64 },
+
+    /// A target speed in columns / second, i.e. cell widths per second.
+    ///
+    /// Cells are usually taller than they are wide, so this is converted to rows with the
+    /// cell aspect ratio (see [`Rain::with_cell_aspect`]).
+    ColumnsPerSecond { speed: f64 },
+
     /// A fast rain. Equivalent to `Absolute { speed: 20.0 }`.
     Fast,
 
@@ -65,10 +253,12 @@ pub enum RainSpeed {
 }
 
 impl RainSpeed {
-    /// Get the absolute speed.
-    fn speed(&self) -> f64 {
+    /// Get the absolute speed in rows / second, given the cell aspect ratio.
+    fn speed(&self, cell_aspect: f64) -> f64 {
         match self {
             RainSpeed::Absolute { speed } => *speed,
+            RainSpeed::RowsPerSecond { speed } => *speed,
+            RainSpeed::ColumnsPerSecond { speed } => *speed / cell_aspect,
             RainSpeed::Fast => 20.0,
             RainSpeed::Normal => 10.0,
             RainSpeed::Slow => 5.0,
@@ -76,6 +266,92 @@ impl RainSpeed {
     }
 }
 
+/// The names of speeds, for parsing and formatting.
+///
+/// Parsing a speed accepts these names, or `absolute:<speed>`, `rows:<speed>` or
+/// `columns:<speed>` in cells per second, ignoring case. Formatting a speed gives the same
+/// names, so speeds round-trip through strings, e.g. in config files:
+///
+/// ```
+/// use tui_rain::RainSpeed;
+///
+/// let speed: RainSpeed = "absolute:12.5".parse().unwrap();
+/// assert_eq!(speed, RainSpeed::Absolute { speed: 12.5 });
+///
+/// for speed in [
+///     RainSpeed::Absolute { speed: 12.5 },
+///     RainSpeed::RowsPerSecond { speed: 0.1 },
+///     RainSpeed::ColumnsPerSecond { speed: 30.0 },
+///     RainSpeed::Fast,
+///     RainSpeed::Normal,
+///     RainSpeed::Slow,
+/// ] {
+///     assert_eq!(speed.to_string().parse::<RainSpeed>(), Ok(speed));
+/// }
+///
+/// assert!("absolute:fast".parse::<RainSpeed>().is_err());
+/// ```
+impl FromStr for RainSpeed {
+    type Err = ParsePresetError;
+
+    fn from_str(s: &str) -> Result<RainSpeed, ParsePresetError> {
+        let error = || ParsePresetError {
+            input: s.to_string(),
+            expected: "fast, normal, slow, absolute:<speed>, rows:<speed>, or columns:<speed>",
+        };
+        if let Some((name, value)) = s.split_once(':') {
+            let speed: f64 = value.trim().parse().map_err(|_| error())?;
+            if !speed.is_finite() {
+                return Err(error());
+            }
+            return match name.to_ascii_lowercase().as_str() {
+                "absolute" => Ok(RainSpeed::Absolute { speed }),
+                "rows" => Ok(RainSpeed::RowsPerSecond { speed }),
+                "columns" => Ok(RainSpeed::ColumnsPerSecond { speed }),
+                _ => Err(error()),
+            };
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "fast" => Ok(RainSpeed::Fast),
+            "normal" => Ok(RainSpeed::Normal),
+            "slow" => Ok(RainSpeed::Slow),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for RainSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RainSpeed::Absolute { speed } => write!(f, "absolute:{speed}"),
+            RainSpeed::RowsPerSecond { speed } => write!(f, "rows:{speed}"),
+            RainSpeed::ColumnsPerSecond { speed } => write!(f, "columns:{speed}"),
+            RainSpeed::Fast => f.write_str("fast"),
+            RainSpeed::Normal => f.write_str("normal"),
+            RainSpeed::Slow => f.write_str("slow"),
+        }
+    }
+}
+
+/// The error for a string that isn't a valid [`RainDensity`] or [`RainSpeed`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct ParsePresetError {
+    input: String,
+    expected: &'static str,
+}
+
+impl fmt::Display for ParsePresetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid preset {:?}, expected {}",
+            self.input, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParsePresetError {}
+
 /// A character set for the rain.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub enum CharacterSet {
@@ -83,6 +359,29 @@ pub enum CharacterSet {
     Explicit { options: Vec<char> },
 
     /// A range of unicode values.
+    ///
+    /// Ranges extending past the last unicode value (`0x10FFFF`) are cut off there, and
+    /// any values that aren't valid characters (i.e. surrogates) are rendered as the
+    /// replacement character `�`:
+    ///
+    /// ```
+    /// use tui_rain::CharacterSet;
+    ///
+    /// let character_set = CharacterSet::UnicodeRange {
+    ///     start: 0x10FFF0,
+    ///     len: 100,
+    /// };
+    ///
+    /// assert_eq!(character_set.size(), 16);
+    /// assert_eq!(character_set.get(99), '\u{10FFF3}');
+    ///
+    /// let surrogates = CharacterSet::UnicodeRange {
+    ///     start: 0xD800,
+    ///     len: 10,
+    /// };
+    ///
+    /// assert_eq!(surrogates.get(0), char::REPLACEMENT_CHARACTER);
+    /// ```
     UnicodeRange { start: u32, len: u32 },
 
     /// Half-width Japanese Kana characters. This is the closest to the original.
@@ -94,14 +393,35 @@ pub enum CharacterSet {
     ///
     /// Equivalent to `CharacterSet::UnicodeRange { start: 0x61, len: 26 }`.
     Lowercase,
+
+    /// A custom function mapping seeds to characters.
+    ///
+    /// The function receives seeds in `[0, size)`, and `size` is the number of distinct
+    /// characters it produces.
+    Custom { map: CharacterFn, size: u32 },
 }
 
 impl CharacterSet {
-    fn get(&self, seed: u32) -> char {
+    /// Get the character for a given seed.
+    ///
+    /// Seeds map to characters in order, wrapping around after [`size`](Self::size)
+    /// characters:
+    ///
+    /// ```
+    /// use tui_rain::CharacterSet;
+    ///
+    /// let character_set = CharacterSet::Lowercase;
+    ///
+    /// assert_eq!(character_set.get(0), 'a');
+    /// assert_eq!(character_set.get(27), 'b');
+    /// ```
+    pub fn get(&self, seed: u32) -> char {
         match self {
             CharacterSet::Explicit { options } => options[seed as usize % options.len()],
             CharacterSet::UnicodeRange { start, len } => {
-                char::from_u32((seed % len) + start).unwrap()
+                let len = CharacterSet::unicode_range_len(*start, *len);
+                char::from_u32(start.saturating_add(seed % len))
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
             }
             CharacterSet::HalfKana => CharacterSet::UnicodeRange {
                 start: 0xFF66,
@@ -113,207 +433,3966 @@ impl CharacterSet {
                 len: 26,
             }
             .get(seed),
+            CharacterSet::Custom { map, size } => (map.0)(seed % (*size).max(1)),
         }
     }
 
-    fn size(&self) -> usize {
+    /// Get the number of distinct characters in the set.
+    ///
+    /// ```
+    /// use tui_rain::CharacterSet;
+    ///
+    /// assert_eq!(CharacterSet::HalfKana.size(), 56);
+    /// ```
+    pub fn size(&self) -> usize {
         match self {
             CharacterSet::Explicit { options } => options.len(),
-            CharacterSet::UnicodeRange { start: _, len } => *len as usize,
+            CharacterSet::UnicodeRange { start, len } => {
+                CharacterSet::unicode_range_len(*start, *len) as usize
+            }
             CharacterSet::HalfKana => 56,
             CharacterSet::Lowercase => 26,
+            CharacterSet::Custom { map: _, size } => *size as usize,
         }
     }
+
+    /// Get the length of a unicode range, cut off after the last unicode value.
+    ///
+    /// The length is at least 1 so it can be used as a modulus.
+    fn unicode_range_len(start: u32, len: u32) -> u32 {
+        len.min(0x110000u32.saturating_sub(start)).max(1)
+    }
 }
 
-#[derive(Clone, PartialEq, Debug)]
-pub struct Rain {
-    elapsed: Duration,
-    seed: u64,
-    rain_density: RainDensity,
-    rain_speed: RainSpeed,
-    rain_speed_variance: f64,
-    tail_lifespan: Duration,
-    color: Color,
-    head_color: Color,
-    bold_dim_effect: bool,
-    noise_interval: Duration,
-    character_set: CharacterSet,
+/// A shared value compared, ordered and hashed by its address rather than its contents.
+///
+/// Functions can't be compared, so wrappers around them use this to treat clones as
+/// equal and everything else as distinct.
+struct ByAddress<F: ?Sized>(Arc<F>);
+
+impl<F: ?Sized> ByAddress<F> {
+    /// The address of the value, which identifies it.
+    fn addr(&self) -> *const () {
+        Arc::as_ptr(&self.0) as *const ()
+    }
 }
 
-impl Rain {
-    /// Construct a new rain widget with defaults for matrix rain.
-    pub fn new_matrix(elapsed: Duration) -> Rain {
-        Rain {
-            elapsed,
-            seed: 1234,
-            rain_density: RainDensity::Normal,
-            rain_speed: RainSpeed::Slow,
-            rain_speed_variance: 0.5,
-            tail_lifespan: Duration::from_secs(2),
-            color: Color::LightGreen,
-            head_color: Color::White,
-            bold_dim_effect: true,
-            noise_interval: Duration::from_secs(5),
-            character_set: CharacterSet::HalfKana,
-        }
+impl<F: ?Sized> Clone for ByAddress<F> {
+    fn clone(&self) -> Self {
+        ByAddress(Arc::clone(&self.0))
     }
+}
 
-    /// Construct a new rain widget with defaults for standard rain.
-    pub fn new_rain(elapsed: Duration) -> Rain {
-        Rain {
-            elapsed,
-            seed: 1234,
-            rain_density: RainDensity::Dense,
-            rain_speed: RainSpeed::Fast,
-            rain_speed_variance: 0.5,
-            tail_lifespan: Duration::from_millis(250),
-            color: Color::LightBlue,
-            head_color: Color::White,
-            bold_dim_effect: true,
-            noise_interval: Duration::from_secs(1),
-            character_set: CharacterSet::UnicodeRange {
-                start: 0x7c,
-                len: 1,
-            },
-        }
+impl<F: ?Sized> Deref for ByAddress<F> {
+    type Target = F;
+
+    fn deref(&self) -> &F {
+        &self.0
     }
+}
 
-    /// Construct a new rain widget with defaults for snow.
-    pub fn new_snow(elapsed: Duration) -> Rain {
-        Rain {
-            elapsed,
-            seed: 1234,
-            rain_density: RainDensity::Dense,
-            rain_speed: RainSpeed::Absolute { speed: 2.0 },
-            rain_speed_variance: 0.1,
-            tail_lifespan: Duration::from_millis(500),
-            color: Color::White,
-            head_color: Color::White,
-            bold_dim_effect: true,
-            noise_interval: Duration::from_secs(1),
-            character_set: CharacterSet::UnicodeRange {
-                start: 0x2a,
-                len: 1,
-            },
-        }
+impl<F: ?Sized> PartialEq for ByAddress<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.addr() == other.addr()
     }
+}
 
-    /// Construct a new rain widget with defaults for emoji soup.
-    ///
-    /// Terminals that render emojis as two characters wide will not enjoy this.
-    pub fn new_emoji_soup(elapsed: Duration) -> Rain {
-        Rain {
-            elapsed,
-            seed: 1234,
-            rain_density: RainDensity::Dense,
-            rain_speed: RainSpeed::Normal,
-            rain_speed_variance: 0.1,
-            tail_lifespan: Duration::from_millis(500),
-            color: Color::White,
-            head_color: Color::White,
-            bold_dim_effect: true,
-            noise_interval: Duration::from_secs(1),
-            character_set: CharacterSet::UnicodeRange {
-                start: 0x1f600,
-                len: 80,
-            },
-        }
+impl<F: ?Sized> Eq for ByAddress<F> {}
+
+impl<F: ?Sized> PartialOrd for ByAddress<F> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
+}
 
-    /// Set the random seed for the generation.
-    ///
-    /// The random seed can be configured. Given a constant screen size, results should
-    /// be reproducible across executions, operating systems, and architectures.
+impl<F: ?Sized> Ord for ByAddress<F> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.addr().cmp(&other.addr())
+    }
+}
+
+impl<F: ?Sized> Hash for ByAddress<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.addr().hash(state)
+    }
+}
+
+impl<F: ?Sized> Debug for ByAddress<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("..")
+    }
+}
+
+/// A function mapping seeds to characters, for [`CharacterSet::Custom`].
+///
+/// Since functions can't be compared, two `CharacterFn`s are only equal if they're clones
+/// of each other. Ordering and hashing likewise use the identity of the function.
+///
+/// ```
+/// use tui_rain::{CharacterFn, CharacterSet};
+///
+/// let character_set = CharacterSet::Custom {
+///     map: CharacterFn::new(|seed| if seed % 2 == 0 { '0' } else { '1' }),
+///     size: 2,
+/// };
+///
+/// assert_eq!(character_set.get(3), '1');
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct CharacterFn(ByAddress<dyn Fn(u32) -> char + Send + Sync>);
+
+impl CharacterFn {
+    /// Wrap a function mapping seeds to characters.
+    pub fn new(map: impl Fn(u32) -> char + Send + Sync + 'static) -> CharacterFn {
+        CharacterFn(ByAddress(Arc::new(map)))
+    }
+}
+
+/// A function choosing the character set for each row, for
+/// [`Rain::with_character_set_by_row`].
+///
+/// Like [`CharacterFn`], two `CharacterSetFn`s are only equal if they're clones of each
+/// other, and hashing uses the identity of the function.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CharacterSetFn(ByAddress<dyn Fn(u16) -> CharacterSet + Send + Sync>);
+
+impl CharacterSetFn {
+    /// Wrap a function choosing the character set for each row.
+    pub fn new(by_row: impl Fn(u16) -> CharacterSet + Send + Sync + 'static) -> CharacterSetFn {
+        CharacterSetFn(ByAddress(Arc::new(by_row)))
+    }
+}
+
+/// A function combining a glyph with the cell it's drawn over, for
+/// [`Rain::with_cell_merge`].
+///
+/// Like [`CharacterFn`], two `CellMergeFn`s are only equal if they're clones of each
+/// other, and hashing uses the identity of the function.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct CellMergeFn(ByAddress<MergeFn>);
+
+/// The signature of a function combining a glyph with the cell it's drawn over.
+type MergeFn = dyn Fn(&Cell, &Glyph) -> Cell + Send + Sync;
+
+impl CellMergeFn {
+    /// Wrap a function combining a glyph with the cell it's drawn over.
+    pub fn new(merge: impl Fn(&Cell, &Glyph) -> Cell + Send + Sync + 'static) -> CellMergeFn {
+        CellMergeFn(ByAddress(Arc::new(merge)))
+    }
+}
+
+/// A list of colors for the rain, for [`Rain::with_palette`].
+///
+/// Palettes are usually loaded from hex colors, e.g. exported from design tools:
+///
+/// ```
+/// use ratatui::style::Color;
+/// use tui_rain::Palette;
+///
+/// let palette = Palette::from_hex_list(&["#00ff41", "008f11"]).unwrap();
+/// assert_eq!(palette.colors(), [Color::Rgb(0, 255, 65), Color::Rgb(0, 143, 17)]);
+///
+/// let error = Palette::from_hex_list(&["#00ff41", "green"]).unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "invalid hex color \"green\" at index 1, expected six hex digits like \"#00ff41\"",
+/// );
+/// ```
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct Palette {
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    /// Create a palette from a list of colors.
+    pub fn new(colors: Vec<Color>) -> Palette {
+        Palette { colors }
+    }
+
+    /// Create a palette from a list of hex colors, like `"#00ff41"`.
     ///
-    /// ```
-    /// use std::time::Duration;
-    /// use tui_rain::Rain;
+    /// Each entry must be six hex digits, optionally preceded by a `#`. Digits may be upper
+    /// or lower case.
     ///
-    /// let elapsed = Duration::from_secs(5);
+    /// # Errors
     ///
-    /// Rain::new_matrix(elapsed)
-    ///     .with_seed(1234);
-    /// ```
-    pub fn with_seed(mut self, seed: u64) -> Rain {
-        self.seed = seed;
-        self
+    /// Returns an error for the first entry that isn't a valid hex color.
+    pub fn from_hex_list(hex_list: &[&str]) -> Result<Palette, InvalidHexColor> {
+        let colors = hex_list
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                parse_hex(entry).ok_or_else(|| InvalidHexColor {
+                    index,
+                    entry: entry.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Palette { colors })
     }
 
-    /// Set the target density for the rain.
-    ///
-    /// This can be configured as an absolute number of drops:
-    ///
-    /// ```
-    /// use std::time::Duration;
-    /// use tui_rain::{Rain, RainDensity};
+    /// The colors in the palette.
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+}
+
+/// The error for an invalid entry in [`Palette::from_hex_list`].
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct InvalidHexColor {
+    /// The index of the invalid entry in the list.
+    pub index: usize,
+
+    /// The invalid entry.
+    pub entry: String,
+}
+
+impl fmt::Display for InvalidHexColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid hex color {:?} at index {}, expected six hex digits like \"#00ff41\"",
+            self.entry, self.index
+        )
+    }
+}
+
+impl std::error::Error for InvalidHexColor {}
+
+/// What the noise effect changes about glyphs over time.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum NoiseMode {
+    /// Glyphs periodically switch to a different character.
+    Character,
+
+    /// Glyphs keep a stable character, and periodically change brightness instead.
     ///
-    /// Rain::new_matrix(Duration::from_secs(0))
-    ///     .with_rain_density(RainDensity::Absolute {
-    ///         num_drops: 100,
-    ///     });
-    /// ```
+    /// Cycles each glyph between bold, normal, and dim. This replaces the positional
+    /// [bold / dim effect](Rain::with_bold_dim_effect), and produces a twinkle.
+    Brightness,
+}
+
+/// What drives the noise effect forward.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum NoiseDriver {
+    /// Noise advances with the elapsed time.
+    Elapsed,
+
+    /// Noise advances with how far each drop is through its current cycle.
+    Progress,
+}
+
+/// How drops behave when they reach the bottom of the screen.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum WrapMode {
+    /// Drops fall continuously, with the tail trailing off the bottom of the screen.
+    Wrap,
+
+    /// Drops vanish entirely once the head leaves the screen, and respawn at the top.
+    Clip,
+}
+
+/// How the speeds of individual drops are distributed around the target speed.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum SpeedDistribution {
+    /// Speeds are uniformly distributed within the rain speed variance of the target.
+    Uniform,
+
+    /// Speeds are normally distributed around the target, with a standard deviation
+    /// given as a fraction of the target speed.
+    Normal { stddev: f64 },
+
+    /// Speeds are exponentially distributed, with a mean of the target speed.
+    Exponential,
+}
+
+/// How the brightness of glyphs is styled.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum StyleMode {
+    /// Glyphs are made bold or dim to vary their brightness.
+    Modifiers,
+
+    /// Glyphs are never bold or dim, and only their colors vary their brightness.
+    ColorOnly,
+}
+
+/// Which side of a drop's head its tail is drawn on.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum TailDirection {
+    /// The tail trails behind the head, like a streak.
+    Behind,
+
+    /// The tail extends ahead of the head, in the direction it's moving.
+    Ahead,
+}
+
+/// The direction drops move in, for [`Rain::with_direction`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum RainDirection {
+    /// Drops fall from the top of the screen to the bottom.
+    Down,
+
+    /// Drops rise from the bottom of the screen to the top.
+    Up,
+}
+
+/// The number of colors a terminal can display, for [`Rain::with_color_depth`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum ColorDepth {
+    /// The 16 named ANSI colors.
+    Ansi16,
+
+    /// The 256 indexed colors.
+    Ansi256,
+
+    /// Any RGB color.
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Map a color to the nearest color at this depth.
+    ///
+    /// Colors that are already displayable at this depth are unchanged, as is
+    /// `Color::Reset`:
+    ///
+    /// ```
+    /// use ratatui::style::Color;
+    /// use tui_rain::ColorDepth;
+    ///
+    /// assert_eq!(ColorDepth::Ansi16.quantize(Color::Rgb(250, 10, 10)), Color::LightRed);
+    /// assert_eq!(ColorDepth::Ansi256.quantize(Color::Rgb(250, 10, 10)), Color::Indexed(196));
+    /// assert_eq!(ColorDepth::TrueColor.quantize(Color::Rgb(250, 10, 10)), Color::Rgb(250, 10, 10));
+    /// ```
+    pub fn quantize(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::TrueColor, _) | (_, Color::Reset) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => {
+                Color::Indexed(nearest_indexed((r, g, b)))
+            }
+            (ColorDepth::Ansi256, _) => color,
+            (ColorDepth::Ansi16, Color::Rgb(..) | Color::Indexed(16..)) => {
+                let rgb = to_rgb(color).expect("RGB and indexed colors convert to RGB");
+                ANSI_16
+                    .into_iter()
+                    .min_by_key(|named| color_distance(rgb, to_rgb(*named).unwrap()))
+                    .unwrap()
+            }
+            (ColorDepth::Ansi16, Color::Indexed(i)) => ANSI_16[i as usize],
+            (ColorDepth::Ansi16, _) => color,
+        }
+    }
+}
+
+/// The 16 named ANSI colors, in the order of their indexes.
+const ANSI_16: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// How a drop's head position is rounded to a row.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Rounding {
+    /// Round down, so the head only reaches a row once it's fully there.
+    Floor,
+
+    /// Round to the nearest row, centering the head's motion on each row.
+    Nearest,
+}
+
+/// An axis to mirror the rain across.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Axis {
+    /// The vertical centerline, reflecting the left half onto the right.
+    Vertical,
+
+    /// The horizontal centerline, reflecting the top half onto the bottom.
+    Horizontal,
+}
+
+/// The precision drop heads are rendered at within a cell.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum SubCell {
+    /// Heads occupy whole cells, and use the character set.
+    Whole,
+
+    /// Heads are rendered as a row of braille dots, with 4 rows per cell.
+    Braille,
+}
+
+/// Every option of a rain, to construct it all at once with [`Rain::from_config`].
+///
+/// Each field is documented by its setter on [`Rain`], and options that are off by
+/// default are `None`. The default is the configuration of [`Rain::new_matrix`], and
+/// [`Rain::config`] gets the configuration of any rain. More fields may be added in
+/// future versions, so set only the fields you need and fill in the rest with
+/// `..RainConfig::default()`:
+///
+/// ```
+/// use tui_rain::{RainConfig, RainDensity};
+///
+/// let config = RainConfig {
+///     rain_density: RainDensity::Sparse,
+///     ..RainConfig::default()
+/// };
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct RainConfig {
+    /// The random seed, as in [`Rain::with_seed`].
+    pub seed: u64,
+
+    /// The density of drops, as in [`Rain::with_rain_density`].
+    pub rain_density: RainDensity,
+
+    /// The speed of drops, as in [`Rain::with_rain_speed`].
+    pub rain_speed: RainSpeed,
+
+    /// The variance of drop speeds, as in [`Rain::with_rain_speed_variance`].
+    pub rain_speed_variance: f64,
+
+    /// The lifespan of the tail, as in [`Rain::with_tail_lifespan`].
+    pub tail_lifespan: Duration,
+
+    /// The color of the tail, as in [`Rain::with_color`].
+    pub color: Color,
+
+    /// The color of the head, as in [`Rain::with_head_color`].
+    pub head_color: Color,
+
+    /// Whether to apply the bold / dim effect, as in [`Rain::with_bold_dim_effect`].
+    pub bold_dim_effect: bool,
+
+    /// The interval between noise changes, as in [`Rain::with_noise_interval`].
+    pub noise_interval: Duration,
+
+    /// The character set, as in [`Rain::with_character_set`].
+    pub character_set: CharacterSet,
+
+    /// How glyphs change over time, as in [`Rain::with_noise_mode`].
+    pub noise_mode: NoiseMode,
+
+    /// What happens to drops at the bottom edge, as in [`Rain::with_wrap_mode`].
+    pub wrap_mode: WrapMode,
+
+    /// The height to width ratio of cells, as in [`Rain::with_cell_aspect`].
+    pub cell_aspect: f64,
+
+    /// Whether to decorrelate each drop's column and phase, as in [`Rain::with_decorrelate`].
+    pub decorrelate: bool,
+
+    /// The fraction of each tail to render, as in [`Rain::with_tail_cutoff`].
+    pub tail_cutoff: f64,
+
+    /// The time window drops spawn within, as in [`Rain::with_active_window`].
+    pub active_window: Option<(Duration, Duration)>,
+
+    /// The color stops along each drop, as in [`Rain::with_color_stops`].
+    pub color_stops: Vec<(f64, Color)>,
+
+    /// Whether to keep drops in separate columns, as in [`Rain::with_no_overlap`].
+    pub no_overlap: bool,
+
+    /// The indexed color gradient along each drop, as in [`Rain::with_indexed_gradient`].
+    pub indexed_gradient: Option<(u8, u8)>,
+
+    /// The wind angle in degrees, as in [`Rain::with_wind_angle`].
+    pub wind_angle: f64,
+
+    /// The amplitude and period of the speed pulse, as in [`Rain::with_speed_pulse`].
+    pub speed_pulse: Option<(f64, Duration)>,
+
+    /// The random number generator, as in [`Rain::with_rng_kind`].
+    pub rng_kind: RngKind,
+
+    /// The range of track lengths, as in [`Rain::with_track_length_range`].
+    pub track_length_range: (f64, f64),
+
+    /// The instance offset combined with the seed, as in [`Rain::with_instance_offset`].
+    pub instance_offset: u64,
+
+    /// Whether drops only enter from the top, as in [`Rain::with_top_entry_only`].
+    pub top_entry_only: bool,
+
+    /// The start and duration of the despawn ramp, as in [`Rain::with_despawn_ramp`].
+    pub despawn_ramp: Option<(Duration, Duration)>,
+
+    /// The precision of drop heads, as in [`Rain::with_subcell`].
+    pub subcell: SubCell,
+
+    /// The longest delay before a drop starts, as in [`Rain::with_start_delay_max`].
+    pub start_delay_max: Duration,
+
+    /// Which glyphs render on top, as in [`Rain::with_glyph_priority`].
+    pub glyph_priority: Priority,
+
+    /// What drives the noise, as in [`Rain::with_noise_driver`].
+    pub noise_driver: NoiseDriver,
+
+    /// The most drops to render, as in [`Rain::with_max_drops`].
+    pub max_drops: Option<usize>,
+
+    /// The opacity of glyphs over the buffer, as in [`Rain::with_blend`].
+    pub blend: f64,
+
+    /// The minimum brightness of colors, as in [`Rain::with_min_brightness`].
+    pub min_brightness: f64,
+
+    /// The number of cells per pixel, as in [`Rain::with_pixel_scale`].
+    pub pixel_scale: u16,
+
+    /// Whether glyphs fall with their drop, as in [`Rain::with_falling_glyphs`].
+    pub falling_glyphs: bool,
+
+    /// The decay and color of ghost trails, as in [`Rain::with_ghost_trail`].
+    pub ghost_trail: Option<(Duration, Color)>,
+
+    /// The speed of the animation clock, as in [`Rain::with_time_scale`].
+    pub time_scale: f64,
+
+    /// The characters left undrawn, as in [`Rain::with_transparent_chars`].
+    pub transparent_chars: Vec<char>,
+
+    /// Whether to write glyphs in scanline order, as in [`Rain::with_scanline_output`].
+    pub scanline_output: bool,
+
+    /// Whether columns are chosen without bias, as in [`Rain::with_uniform_columns`].
+    pub uniform_columns: bool,
+
+    /// The distribution of drop speeds, as in [`Rain::with_speed_distribution`].
+    pub speed_distribution: SpeedDistribution,
+
+    /// The rate and color of sparkles, as in [`Rain::with_sparkle`].
+    pub sparkle: Option<(f64, Color)>,
+
+    /// The axis to mirror the rain across, as in [`Rain::with_mirror`].
+    pub mirror: Option<Axis>,
+
+    /// The offset of the animation clock, as in [`Rain::with_time_offset`].
+    pub time_offset: Duration,
+
+    /// Whether to only render heads, as in [`Rain::with_heads_only`].
+    pub heads_only: bool,
+
+    /// How the bold / dim effect is styled, as in [`Rain::with_style_mode`].
+    pub style_mode: StyleMode,
+
+    /// The period and densities of the storm cycle, as in [`Rain::with_storm_cycle`].
+    pub storm_cycle: Option<(Duration, RainDensity, RainDensity)>,
+
+    /// The character set for each row, as in [`Rain::with_character_set_by_row`].
+    pub character_set_by_row: Option<CharacterSetFn>,
+
+    /// The minimum interval between computed frames, as in [`Rain::with_min_frame_interval`].
+    pub min_frame_interval: Option<Duration>,
+
+    /// The strength of the vignette, as in [`Rain::with_vignette`].
+    pub vignette: f64,
+
+    /// Whether drops bounce off the bottom, as in [`Rain::with_bounce`].
+    pub bounce: bool,
+
+    /// Whether to shuffle the character set, as in [`Rain::with_shuffled_charset`].
+    pub shuffled_charset: bool,
+
+    /// The velocity of drops, as in [`Rain::with_velocity`].
+    pub velocity: Option<(f64, f64)>,
+
+    /// The most columns a drop drifts between falls, as in [`Rain::with_column_drift`].
+    pub column_drift: Option<i16>,
+
+    /// The function merging glyphs with cells, as in [`Rain::with_cell_merge`].
+    pub cell_merge: Option<CellMergeFn>,
+
+    /// Whether to clear the area first, as in [`Rain::with_clear`].
+    pub clear: bool,
+
+    /// The weights of tail lengths, as in [`Rain::with_tail_length_weights`].
+    pub tail_length_weights: Vec<(u16, f64)>,
+
+    /// Whether to color drops by age, for debugging, as in [`Rain::with_debug_age_coloring`].
+    pub debug_age_coloring: bool,
+
+    /// The character and color of head shadows, as in [`Rain::with_head_shadow`].
+    pub head_shadow: Option<(char, Color)>,
+
+    /// The mask of cells to render, as in [`Rain::with_shape_mask`].
+    pub shape_mask: Option<Vec<Vec<bool>>>,
+
+    /// How head positions are rounded to rows, as in [`Rain::with_head_rounding`].
+    pub head_rounding: Rounding,
+
+    /// Which way tails extend from heads, as in [`Rain::with_tail_direction`].
+    pub tail_direction: TailDirection,
+
+    /// The color depth of the terminal, as in [`Rain::with_color_depth`].
+    pub color_depth: ColorDepth,
+
+    /// Whether to invert the gradient, as in [`Rain::with_invert_gradient`].
+    pub invert_gradient: bool,
+
+    /// The time budget for building a frame, as in [`Rain::with_time_budget`].
+    pub time_budget: Option<Duration>,
+
+    /// The density of the fog, as in [`Rain::with_fog`].
+    pub fog: f64,
+
+    /// Whether track lengths vary between cycles, as in [`Rain::with_varying_tracks`].
+    pub varying_tracks: bool,
+
+    /// The rate of glitches, as in [`Rain::with_glitch`].
+    pub glitch: f64,
+
+    /// The fewest drops to render, as in [`Rain::with_min_drops`].
+    pub min_drops: usize,
+
+    /// The height and dimming of the reflection, as in [`Rain::with_reflection`].
+    pub reflection: Option<(u16, f64)>,
+
+    /// The number of cycles each drop lives, as in [`Rain::with_drop_lifespan`].
+    pub drop_lifespan: Option<u32>,
+
+    /// The number of bold rows at each head, as in [`Rain::with_bold_rows`].
+    pub bold_rows: Option<u16>,
+
+    /// The number of dim rows at the end of each tail, as in [`Rain::with_dim_rows`].
+    pub dim_rows: Option<u16>,
+
+    /// The direction drops fall, as in [`Rain::with_direction`].
+    pub direction: RainDirection,
+}
+
+impl Default for RainConfig {
+    fn default() -> RainConfig {
+        Rain::new_matrix(Duration::ZERO).config()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Rain {
+    elapsed: Duration,
+    seed: u64,
+    rain_density: RainDensity,
+    rain_speed: RainSpeed,
+    rain_speed_variance: f64,
+    tail_lifespan: Duration,
+    color: Color,
+    head_color: Color,
+    bold_dim_effect: bool,
+    noise_interval: Duration,
+    character_set: CharacterSet,
+    noise_mode: NoiseMode,
+    wrap_mode: WrapMode,
+    cell_aspect: f64,
+    decorrelate: bool,
+    tail_cutoff: f64,
+    active_window: Option<(Duration, Duration)>,
+    color_stops: Vec<(f64, Color)>,
+    no_overlap: bool,
+    indexed_gradient: Option<(u8, u8)>,
+    wind_angle: f64,
+    speed_pulse: Option<(f64, Duration)>,
+    rng_kind: RngKind,
+    track_length_range: (f64, f64),
+    instance_offset: u64,
+    top_entry_only: bool,
+    despawn_ramp: Option<(Duration, Duration)>,
+    subcell: SubCell,
+    start_delay_max: Duration,
+    glyph_priority: Priority,
+    noise_driver: NoiseDriver,
+    max_drops: Option<usize>,
+    blend: f64,
+    min_brightness: f64,
+    pixel_scale: u16,
+    falling_glyphs: bool,
+    ghost_trail: Option<(Duration, Color)>,
+    time_scale: f64,
+    transparent_chars: Vec<char>,
+    scanline_output: bool,
+    uniform_columns: bool,
+    speed_distribution: SpeedDistribution,
+    sparkle: Option<(f64, Color)>,
+    mirror: Option<Axis>,
+    time_offset: Duration,
+    heads_only: bool,
+    style_mode: StyleMode,
+    storm_cycle: Option<(Duration, RainDensity, RainDensity)>,
+    character_set_by_row: Option<CharacterSetFn>,
+    min_frame_interval: Option<Duration>,
+    vignette: f64,
+    bounce: bool,
+    shuffled_charset: bool,
+    velocity: Option<(f64, f64)>,
+    column_drift: Option<i16>,
+    cell_merge: Option<CellMergeFn>,
+    clear: bool,
+    tail_length_weights: Vec<(u16, f64)>,
+    debug_age_coloring: bool,
+    head_shadow: Option<(char, Color)>,
+    shape_mask: Option<Vec<Vec<bool>>>,
+    head_rounding: Rounding,
+    tail_direction: TailDirection,
+    color_depth: ColorDepth,
+    invert_gradient: bool,
+    time_budget: Option<Duration>,
+    fog: f64,
+    varying_tracks: bool,
+    glitch: f64,
+    min_drops: usize,
+    reflection: Option<(u16, f64)>,
+    drop_lifespan: Option<u32>,
+    bold_rows: Option<u16>,
+    dim_rows: Option<u16>,
+    direction: RainDirection,
+}
+
+impl Rain {
+    /// Construct a rain with the options shared by every preset, for the presets to
+    /// override the look of.
+    fn defaults(elapsed: Duration) -> Rain {
+        Rain {
+            elapsed,
+            seed: 1234,
+            rain_density: RainDensity::Normal,
+            rain_speed: RainSpeed::Slow,
+            rain_speed_variance: 0.5,
+            tail_lifespan: Duration::from_secs(2),
+            color: Color::LightGreen,
+            head_color: Color::White,
+            bold_dim_effect: true,
+            noise_interval: Duration::from_secs(5),
+            character_set: CharacterSet::HalfKana,
+            noise_mode: NoiseMode::Character,
+            wrap_mode: WrapMode::Wrap,
+            cell_aspect: 1.0,
+            decorrelate: false,
+            tail_cutoff: 1.0,
+            active_window: None,
+            color_stops: vec![],
+            no_overlap: false,
+            indexed_gradient: None,
+            wind_angle: 0.0,
+            speed_pulse: None,
+            rng_kind: RngKind::PcgFast,
+            track_length_range: (1.5, 2.5),
+            instance_offset: 0,
+            top_entry_only: false,
+            despawn_ramp: None,
+            subcell: SubCell::Whole,
+            start_delay_max: Duration::ZERO,
+            glyph_priority: Priority::Newest,
+            noise_driver: NoiseDriver::Elapsed,
+            max_drops: None,
+            blend: 1.0,
+            min_brightness: 0.0,
+            pixel_scale: 1,
+            falling_glyphs: false,
+            ghost_trail: None,
+            time_scale: 1.0,
+            transparent_chars: vec![],
+            scanline_output: false,
+            uniform_columns: false,
+            speed_distribution: SpeedDistribution::Uniform,
+            sparkle: None,
+            mirror: None,
+            time_offset: Duration::ZERO,
+            heads_only: false,
+            style_mode: StyleMode::Modifiers,
+            storm_cycle: None,
+            character_set_by_row: None,
+            min_frame_interval: None,
+            vignette: 0.0,
+            bounce: false,
+            shuffled_charset: false,
+            velocity: None,
+            column_drift: None,
+            cell_merge: None,
+            clear: false,
+            tail_length_weights: vec![],
+            debug_age_coloring: false,
+            head_shadow: None,
+            shape_mask: None,
+            head_rounding: Rounding::Floor,
+            tail_direction: TailDirection::Behind,
+            color_depth: ColorDepth::TrueColor,
+            invert_gradient: false,
+            time_budget: None,
+            fog: 0.0,
+            varying_tracks: false,
+            glitch: 0.0,
+            min_drops: 0,
+            reflection: None,
+            drop_lifespan: None,
+            bold_rows: None,
+            dim_rows: None,
+            direction: RainDirection::Down,
+        }
+    }
+
+    /// Construct a new rain widget with defaults for matrix rain.
+    pub fn new_matrix(elapsed: Duration) -> Rain {
+        Rain {
+            rain_density: RainDensity::Normal,
+            rain_speed: RainSpeed::Slow,
+            rain_speed_variance: 0.5,
+            tail_lifespan: Duration::from_secs(2),
+            color: Color::LightGreen,
+            head_color: Color::White,
+            noise_interval: Duration::from_secs(5),
+            character_set: CharacterSet::HalfKana,
+            ..Rain::defaults(elapsed)
+        }
+    }
+
+    /// Construct a new rain widget with defaults for standard rain.
+    pub fn new_rain(elapsed: Duration) -> Rain {
+        Rain {
+            rain_density: RainDensity::Dense,
+            rain_speed: RainSpeed::Fast,
+            rain_speed_variance: 0.5,
+            tail_lifespan: Duration::from_millis(250),
+            color: Color::LightBlue,
+            head_color: Color::White,
+            noise_interval: Duration::from_secs(1),
+            character_set: CharacterSet::UnicodeRange {
+                start: 0x7c,
+                len: 1,
+            },
+            ..Rain::defaults(elapsed)
+        }
+    }
+
+    /// Construct a new rain widget with defaults for snow.
+    pub fn new_snow(elapsed: Duration) -> Rain {
+        Rain {
+            rain_density: RainDensity::Dense,
+            rain_speed: RainSpeed::Absolute { speed: 2.0 },
+            rain_speed_variance: 0.1,
+            tail_lifespan: Duration::from_millis(500),
+            color: Color::White,
+            head_color: Color::White,
+            noise_interval: Duration::from_secs(1),
+            character_set: CharacterSet::UnicodeRange {
+                start: 0x2a,
+                len: 1,
+            },
+            ..Rain::defaults(elapsed)
+        }
+    }
+
+    /// Construct a new rain widget with defaults for a barely-there drizzle.
+    pub fn new_drizzle(elapsed: Duration) -> Rain {
+        Rain {
+            rain_density: RainDensity::Drizzle,
+            rain_speed: RainSpeed::Slow,
+            rain_speed_variance: 0.3,
+            tail_lifespan: Duration::from_millis(500),
+            color: Color::DarkGray,
+            head_color: Color::Gray,
+            noise_interval: Duration::from_secs(1),
+            character_set: CharacterSet::UnicodeRange {
+                start: 0x7c,
+                len: 1,
+            },
+            ..Rain::defaults(elapsed)
+        }
+    }
+
+    /// Construct a new rain widget with defaults for emoji soup.
+    ///
+    /// Terminals that render emojis as two characters wide will not enjoy this.
+    pub fn new_emoji_soup(elapsed: Duration) -> Rain {
+        Rain {
+            rain_density: RainDensity::Dense,
+            rain_speed: RainSpeed::Normal,
+            rain_speed_variance: 0.1,
+            tail_lifespan: Duration::from_millis(500),
+            color: Color::White,
+            head_color: Color::White,
+            noise_interval: Duration::from_secs(1),
+            character_set: CharacterSet::UnicodeRange {
+                start: 0x1f600,
+                len: 80,
+            },
+            ..Rain::defaults(elapsed)
+        }
+    }
+
+    /// Construct a new rain widget from a configuration of every option.
+    ///
+    /// This is handy when setting everything at once, e.g. from a settings screen. Any
+    /// options left as their defaults match [`Rain::new_matrix`]:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::{Rain, RainConfig, RainSpeed};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// let config = RainConfig {
+    ///     rain_speed: RainSpeed::Fast,
+    ///     color: Color::LightBlue,
+    ///     ..RainConfig::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Rain::from_config(config, elapsed),
+    ///     Rain::new_matrix(elapsed)
+    ///         .with_rain_speed(RainSpeed::Fast)
+    ///         .with_color(Color::LightBlue),
+    /// );
+    /// ```
+    ///
+    /// Each option is set with its builder method, so this panics on the same invalid
+    /// values they do.
+    pub fn from_config(config: RainConfig, elapsed: Duration) -> Rain {
+        let RainConfig {
+            seed,
+            rain_density,
+            rain_speed,
+            rain_speed_variance,
+            tail_lifespan,
+            color,
+            head_color,
+            bold_dim_effect,
+            noise_interval,
+            character_set,
+            noise_mode,
+            wrap_mode,
+            cell_aspect,
+            decorrelate,
+            tail_cutoff,
+            active_window,
+            color_stops,
+            no_overlap,
+            indexed_gradient,
+            wind_angle,
+            speed_pulse,
+            rng_kind,
+            track_length_range,
+            instance_offset,
+            top_entry_only,
+            despawn_ramp,
+            subcell,
+            start_delay_max,
+            glyph_priority,
+            noise_driver,
+            max_drops,
+            blend,
+            min_brightness,
+            pixel_scale,
+            falling_glyphs,
+            ghost_trail,
+            time_scale,
+            transparent_chars,
+            scanline_output,
+            uniform_columns,
+            speed_distribution,
+            sparkle,
+            mirror,
+            time_offset,
+            heads_only,
+            style_mode,
+            storm_cycle,
+            character_set_by_row,
+            min_frame_interval,
+            vignette,
+            bounce,
+            shuffled_charset,
+            velocity,
+            column_drift,
+            cell_merge,
+            clear,
+            tail_length_weights,
+            debug_age_coloring,
+            head_shadow,
+            shape_mask,
+            head_rounding,
+            tail_direction,
+            color_depth,
+            invert_gradient,
+            time_budget,
+            fog,
+            varying_tracks,
+            glitch,
+            min_drops,
+            reflection,
+            drop_lifespan,
+            bold_rows,
+            dim_rows,
+            direction,
+        } = config;
+        let mut rain = Rain::defaults(elapsed)
+            .with_seed(seed)
+            .with_rain_density(rain_density)
+            .with_rain_speed(rain_speed)
+            .with_rain_speed_variance(rain_speed_variance)
+            .with_tail_lifespan(tail_lifespan)
+            .with_color(color)
+            .with_head_color(head_color)
+            .with_bold_dim_effect(bold_dim_effect)
+            .with_noise_interval(noise_interval)
+            .with_character_set(character_set)
+            .with_noise_mode(noise_mode)
+            .with_wrap_mode(wrap_mode)
+            .with_cell_aspect(cell_aspect)
+            .with_decorrelate(decorrelate)
+            .with_tail_cutoff(tail_cutoff)
+            .with_color_stops(color_stops)
+            .with_no_overlap(no_overlap)
+            .with_wind_angle(wind_angle)
+            .with_rng_kind(rng_kind)
+            .with_track_length_range(track_length_range.0, track_length_range.1)
+            .with_instance_offset(instance_offset)
+            .with_top_entry_only(top_entry_only)
+            .with_subcell(subcell)
+            .with_start_delay_max(start_delay_max)
+            .with_glyph_priority(glyph_priority)
+            .with_noise_driver(noise_driver)
+            .with_blend(blend)
+            .with_min_brightness(min_brightness)
+            .with_pixel_scale(pixel_scale)
+            .with_falling_glyphs(falling_glyphs)
+            .with_time_scale(time_scale)
+            .with_transparent_chars(transparent_chars)
+            .with_scanline_output(scanline_output)
+            .with_uniform_columns(uniform_columns)
+            .with_speed_distribution(speed_distribution)
+            .with_time_offset(time_offset)
+            .with_heads_only(heads_only)
+            .with_style_mode(style_mode)
+            .with_vignette(vignette)
+            .with_bounce(bounce)
+            .with_shuffled_charset(shuffled_charset)
+            .with_clear(clear)
+            .with_tail_length_weights(tail_length_weights)
+            .with_debug_age_coloring(debug_age_coloring)
+            .with_head_rounding(head_rounding)
+            .with_tail_direction(tail_direction)
+            .with_color_depth(color_depth)
+            .with_invert_gradient(invert_gradient)
+            .with_fog(fog)
+            .with_varying_tracks(varying_tracks)
+            .with_glitch(glitch)
+            .with_min_drops(min_drops)
+            .with_direction(direction);
+        if let Some((start, end)) = active_window {
+            rain = rain.with_active_window(start, end);
+        }
+        if let Some((start, end)) = indexed_gradient {
+            rain = rain.with_indexed_gradient(start, end);
+        }
+        if let Some((amplitude, period)) = speed_pulse {
+            rain = rain.with_speed_pulse(amplitude, period);
+        }
+        if let Some((start, duration)) = despawn_ramp {
+            rain = rain.with_despawn_ramp(start, duration);
+        }
+        if let Some(max_drops) = max_drops {
+            rain = rain.with_max_drops(max_drops);
+        }
+        if let Some((decay, color)) = ghost_trail {
+            rain = rain.with_ghost_trail(decay, color);
+        }
+        if let Some((rate, color)) = sparkle {
+            rain = rain.with_sparkle(rate, color);
+        }
+        if let Some(mirror) = mirror {
+            rain = rain.with_mirror(mirror);
+        }
+        if let Some((period, calm_density, storm_density)) = storm_cycle {
+            rain = rain.with_storm_cycle(period, calm_density, storm_density);
+        }
+        if let Some(min_frame_interval) = min_frame_interval {
+            rain = rain.with_min_frame_interval(min_frame_interval);
+        }
+        if let Some((vx, vy)) = velocity {
+            rain = rain.with_velocity(vx, vy);
+        }
+        if let Some(column_drift) = column_drift {
+            rain = rain.with_column_drift(column_drift);
+        }
+        if let Some((shadow, color)) = head_shadow {
+            rain = rain.with_head_shadow(shadow, color);
+        }
+        if let Some(shape_mask) = shape_mask {
+            rain = rain.with_shape_mask(shape_mask);
+        }
+        if let Some(time_budget) = time_budget {
+            rain = rain.with_time_budget(time_budget);
+        }
+        if let Some((height, dim)) = reflection {
+            rain = rain.with_reflection(height, dim);
+        }
+        if let Some(drop_lifespan) = drop_lifespan {
+            rain = rain.with_drop_lifespan(drop_lifespan);
+        }
+        if let Some(bold_rows) = bold_rows {
+            rain = rain.with_bold_rows(bold_rows);
+        }
+        if let Some(dim_rows) = dim_rows {
+            rain = rain.with_dim_rows(dim_rows);
+        }
+        rain.character_set_by_row = character_set_by_row;
+        rain.cell_merge = cell_merge;
+        rain
+    }
+
+    /// Get the configuration of every option, apart from the elapsed duration.
+    ///
+    /// Constructing a rain from its config gives back the same rain, so a configured rain
+    /// can be saved, tweaked, and rebuilt:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    /// let rain = Rain::new_snow(elapsed).with_wind_angle(20.0).with_fog(0.1);
+    ///
+    /// assert_eq!(Rain::from_config(rain.config(), elapsed), rain);
+    /// ```
+    pub fn config(&self) -> RainConfig {
+        let Rain {
+            elapsed: _,
+            seed,
+            rain_density,
+            rain_speed,
+            rain_speed_variance,
+            tail_lifespan,
+            color,
+            head_color,
+            bold_dim_effect,
+            noise_interval,
+            character_set,
+            noise_mode,
+            wrap_mode,
+            cell_aspect,
+            decorrelate,
+            tail_cutoff,
+            active_window,
+            color_stops,
+            no_overlap,
+            indexed_gradient,
+            wind_angle,
+            speed_pulse,
+            rng_kind,
+            track_length_range,
+            instance_offset,
+            top_entry_only,
+            despawn_ramp,
+            subcell,
+            start_delay_max,
+            glyph_priority,
+            noise_driver,
+            max_drops,
+            blend,
+            min_brightness,
+            pixel_scale,
+            falling_glyphs,
+            ghost_trail,
+            time_scale,
+            transparent_chars,
+            scanline_output,
+            uniform_columns,
+            speed_distribution,
+            sparkle,
+            mirror,
+            time_offset,
+            heads_only,
+            style_mode,
+            storm_cycle,
+            character_set_by_row,
+            min_frame_interval,
+            vignette,
+            bounce,
+            shuffled_charset,
+            velocity,
+            column_drift,
+            cell_merge,
+            clear,
+            tail_length_weights,
+            debug_age_coloring,
+            head_shadow,
+            shape_mask,
+            head_rounding,
+            tail_direction,
+            color_depth,
+            invert_gradient,
+            time_budget,
+            fog,
+            varying_tracks,
+            glitch,
+            min_drops,
+            reflection,
+            drop_lifespan,
+            bold_rows,
+            dim_rows,
+            direction,
+        } = self.clone();
+        RainConfig {
+            seed,
+            rain_density,
+            rain_speed,
+            rain_speed_variance,
+            tail_lifespan,
+            color,
+            head_color,
+            bold_dim_effect,
+            noise_interval,
+            character_set,
+            noise_mode,
+            wrap_mode,
+            cell_aspect,
+            decorrelate,
+            tail_cutoff,
+            active_window,
+            color_stops,
+            no_overlap,
+            indexed_gradient,
+            wind_angle,
+            speed_pulse,
+            rng_kind,
+            track_length_range,
+            instance_offset,
+            top_entry_only,
+            despawn_ramp,
+            subcell,
+            start_delay_max,
+            glyph_priority,
+            noise_driver,
+            max_drops,
+            blend,
+            min_brightness,
+            pixel_scale,
+            falling_glyphs,
+            ghost_trail,
+            time_scale,
+            transparent_chars,
+            scanline_output,
+            uniform_columns,
+            speed_distribution,
+            sparkle,
+            mirror,
+            time_offset,
+            heads_only,
+            style_mode,
+            storm_cycle,
+            character_set_by_row,
+            min_frame_interval,
+            vignette,
+            bounce,
+            shuffled_charset,
+            velocity,
+            column_drift,
+            cell_merge,
+            clear,
+            tail_length_weights,
+            debug_age_coloring,
+            head_shadow,
+            shape_mask,
+            head_rounding,
+            tail_direction,
+            color_depth,
+            invert_gradient,
+            time_budget,
+            fog,
+            varying_tracks,
+            glitch,
+            min_drops,
+            reflection,
+            drop_lifespan,
+            bold_rows,
+            dim_rows,
+            direction,
+        }
+    }
+
+    /// Set the elapsed duration to render.
+    ///
+    /// This overrides the elapsed duration the widget was constructed with, which is
+    /// handy when a configured widget is reused across frames:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let rain = Rain::new_matrix(Duration::ZERO);
+    ///
+    /// rain.with_elapsed(Duration::from_secs(5));
+    /// ```
+    ///
+    /// When rendered with a [`RainState`], this is the elapsed duration before any
+    /// paused time is subtracted.
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Rain {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Set the elapsed duration to render from a [`RainClock`].
+    ///
+    /// This is the same as [`Rain::with_elapsed`] with the clock's elapsed duration:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainClock};
+    ///
+    /// let mut clock = RainClock::new();
+    /// clock.advance(Duration::from_secs(5));
+    ///
+    /// assert_eq!(
+    ///     Rain::new_matrix(Duration::ZERO).with_clock(&clock),
+    ///     Rain::new_matrix(Duration::from_secs(5)),
+    /// );
+    /// ```
+    pub fn with_clock(self, clock: &RainClock) -> Rain {
+        self.with_elapsed(clock.elapsed())
+    }
+
+    /// Set the random seed for the generation.
+    ///
+    /// The random seed can be configured. Given a constant screen size, results should
+    /// be reproducible across executions, operating systems, and architectures.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_seed(1234);
+    /// ```
+    pub fn with_seed(mut self, seed: u64) -> Rain {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the random seed for the generation from a string, e.g. an ID for a scene.
+    ///
+    /// The string's UTF-8 bytes are hashed into the seed with 64-bit FNV-1a. This hash is
+    /// fixed, so the same string gives the same pattern on every platform and version:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// assert_eq!(
+    ///     Rain::new_matrix(elapsed).with_seed_str("a"),
+    ///     Rain::new_matrix(elapsed).with_seed(0xaf63_dc4c_8601_ec8c),
+    /// );
+    /// ```
+    pub fn with_seed_str(self, seed: &str) -> Rain {
+        self.with_seed(fnv1a(seed.as_bytes()))
+    }
+
+    /// Advance to the next pattern, by deriving a new seed from the current one.
+    ///
+    /// The new seed is one step of the splitmix64 generator from the current seed. That is,
+    /// the seed is incremented by `0x9E37_79B9_7F4A_7C15` then hashed with the splitmix64
+    /// finalizer. Calling this repeatedly gives a reproducible sequence of distinct
+    /// patterns, e.g. for a slideshow of backgrounds:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let rain = Rain::new_matrix(Duration::from_secs(5)).with_seed(0);
+    ///
+    /// assert_eq!(rain.clone().next_pattern(), rain.with_seed(0xe220_a839_7b1d_cdaf));
+    /// ```
+    pub fn next_pattern(self) -> Rain {
+        let seed = mix(self.seed.wrapping_add(0x9E37_79B9_7F4A_7C15));
+        self.with_seed(seed)
+    }
+
+    /// Set an instance offset, to make multiple rains with the same seed look distinct.
+    ///
+    /// Rains with the same seed show identical patterns, which stands out when they're
+    /// placed side by side. Giving each one a different offset keeps them reproducible,
+    /// but visually distinct:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// let left = Rain::new_matrix(elapsed).with_instance_offset(0);
+    /// let right = Rain::new_matrix(elapsed).with_instance_offset(1);
+    /// ```
+    ///
+    /// The offset is hashed and combined with the seed, so the same seed and offset
+    /// always yield the same pattern. An offset of `0` leaves the seed unchanged.
+    pub fn with_instance_offset(mut self, instance_offset: u64) -> Rain {
+        self.instance_offset = instance_offset;
+        self
+    }
+
+    /// Set a random seed for the generation, so the pattern differs on every run.
+    ///
+    /// The default seed is fixed for reproducibility. If reproducibility isn't needed,
+    /// a seed can be drawn with `rand::random`, which is seeded by the operating system:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_random_seed();
+    /// ```
+    ///
+    /// Since the rain is rebuilt every frame, the seed should be chosen once and reused
+    /// via [`Rain::seed`] and [`Rain::with_seed`], otherwise every frame renders a
+    /// different pattern.
+    pub fn with_random_seed(self) -> Rain {
+        self.with_seed(rand::random())
+    }
+
+    /// Get the random seed for the generation.
+    ///
+    /// This is useful for reusing a [random seed](Rain::with_random_seed) across frames:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let seed = Rain::new_matrix(Duration::ZERO).with_random_seed().seed();
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_seed(seed);
+    /// ```
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Set the target density for the rain.
+    ///
+    /// This can be configured as an absolute number of drops:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// Rain::new_matrix(Duration::from_secs(0))
+    ///     .with_rain_density(RainDensity::Absolute {
+    ///         num_drops: 100,
+    ///     });
+    /// ```
     /// Or a ratio of screen pixels to drops (lower is more dense):
     ///
     /// ```
     /// use std::time::Duration;
     /// use tui_rain::{Rain, RainDensity};
     ///
-    /// Rain::new_matrix(Duration::from_secs(0))
-    ///     .with_rain_density(RainDensity::Relative {
-    ///         sparseness: 50,
-    ///     });
+    /// Rain::new_matrix(Duration::from_secs(0))
+    ///     .with_rain_density(RainDensity::Relative {
+    ///         sparseness: 50,
+    ///     });
+    /// ```
+    ///
+    /// The actual number of drops on the screen at any time is randomly distributed
+    /// between 0 and twice the target.
+    ///
+    /// Preset relative options include:
+    ///
+    /// - `RainDensity::Drizzle`
+    /// - `RainDensity::Sparse`
+    /// - `RainDensity::Normal`
+    /// - `RainDensity::Dense`
+    ///
+    /// A density of zero drops renders an empty frame, leaving the buffer untouched:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_rain_density(RainDensity::Absolute { num_drops: 0 })
+    ///     .render(area, &mut buf);
+    ///
+    /// assert_eq!(buf, Buffer::empty(area));
+    /// ```
+    pub fn with_rain_density(mut self, rain_density: RainDensity) -> Rain {
+        self.rain_density = rain_density;
+        self
+    }
+
+    /// Set the target speed for the rain.
+    ///
+    /// Speed can be configured as an absolute value of rows per second, or as a
+    /// preset.
+    ///
+    /// For an absolute speed in rows per second:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rain_speed(RainSpeed::Absolute {
+    ///         speed: 10.0,
+    ///     });
+    /// ```
+    ///
+    /// Preset options include:
+    ///
+    /// - `RainSpeed::Slow`
+    /// - `RainSpeed::Normal`
+    /// - `RainSpeed::Fast`
+    ///
+    /// Absolute speeds are in rows per second, so a drop at speed N moves N rows in a
+    /// second. This can be made explicit with `RainSpeed::RowsPerSecond`, or given in
+    /// cell widths with `RainSpeed::ColumnsPerSecond`, which is converted to rows by the
+    /// cell aspect ratio:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// let area = Rect::new(0, 0, 1, 20);
+    ///
+    /// // Find the row of the (white) head of a single drop after some seconds.
+    /// let head_row = |rain_speed, secs| {
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_secs(secs))
+    ///         .with_rain_speed(rain_speed)
+    ///         .with_rain_speed_variance(0.0)
+    ///         .with_cell_aspect(2.0)
+    ///         .render_single_drop(vec![0; 40], area, &mut buf);
+    ///     (0..area.height).find(|y| buf[(0, *y)].fg == Color::White).unwrap()
+    /// };
+    ///
+    /// let rows = RainSpeed::RowsPerSecond { speed: 4.0 };
+    /// assert_eq!(head_row(rows, 13) - head_row(rows, 11), 8);
+    ///
+    /// let columns = RainSpeed::ColumnsPerSecond { speed: 8.0 };
+    /// assert_eq!(head_row(columns, 13) - head_row(columns, 11), 8);
+    /// ```
+    pub fn with_rain_speed(mut self, rain_speed: RainSpeed) -> Rain {
+        self.rain_speed = rain_speed;
+        self
+    }
+
+    /// Set the rain speed variance.
+    ///
+    /// To avoid perfectly consistent patterns, you can configure some variance in the
+    /// speed of each drop. This can also give an impression of parallax (depth).
+    ///
+    /// For example, a value of `0.1` will cause each drop's speed to be uniformly
+    /// distrbuted within ±10% of the target speed:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rain_speed_variance(0.1);
+    /// ```
+    ///
+    /// The speed of an individual drop will never go below 0.001 rows / second, but
+    /// can vary arbitrarily high.
+    pub fn with_rain_speed_variance(mut self, rain_speed_variance: f64) -> Rain {
+        self.rain_speed_variance = rain_speed_variance;
+        self
+    }
+
+    /// Set the tail lifespan for the rain.
+    ///
+    /// You can make the rain drop tails appear shorter / longer by configuring how long
+    /// the tail effect lasts:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_tail_lifespan(Duration::from_secs(5));
+    /// ```
+    ///
+    /// The drop length is capped at the screen height to avoid strange wraparound
+    /// effects. It's also at least 1, so every drop shows its head even when it's too
+    /// slow or its tail too short to cover a whole row:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// // Drops this slow take many hours to first fall onto the screen.
+    /// Rain::new_matrix(Duration::from_secs(1_000_000))
+    ///     .with_rain_speed(RainSpeed::Absolute { speed: 0.001 })
+    ///     .with_tail_lifespan(Duration::from_secs_f64(0.001))
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().any(|cell| cell.symbol() != " "));
+    /// ```
+    pub fn with_tail_lifespan(mut self, tail_lifespan: Duration) -> Rain {
+        self.tail_lifespan = tail_lifespan;
+        self
+    }
+
+    /// Set the tail lifespan for the rain, in seconds.
+    ///
+    /// This is a convenience for [`Rain::with_tail_lifespan`]:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_tail_lifespan_secs(1.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is negative, overflows a `Duration`, or isn't finite.
+    pub fn with_tail_lifespan_secs(self, tail_lifespan: f64) -> Rain {
+        self.with_tail_lifespan(Duration::from_secs_f64(tail_lifespan))
+    }
+
+    /// Set the color for the rain.
+    ///
+    /// You can change the tail color for each drop:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_color(ratatui::style::Color::LightGreen);
+    /// ```
+    ///
+    /// The color of the head is [independently configured](Rain::with_head_color). The
+    /// bold / dim effects that automatically get applied over a drop's length may tweak
+    /// the color inadvertently, but [this can be disabled](Rain::with_bold_dim_effect).
+    ///
+    /// To blend in with arbitrary themes, `Color::Reset` uses the terminal's default
+    /// foreground color:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_color(Color::Reset)
+    ///     .with_head_color(Color::Reset)
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().any(|cell| cell.symbol() != " "));
+    /// assert!(buf.content().iter().all(|cell| cell.fg == Color::Reset));
+    /// ```
+    pub fn with_color(mut self, color: Color) -> Rain {
+        self.color = color;
+        self
+    }
+
+    /// Set the head color for the rain.
+    ///
+    /// You can change the head color for each drop:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_head_color(ratatui::style::Color::Green);
+    /// ```
+    ///
+    /// Matching the head color to the tail gives uniformly colored drops, with no white
+    /// tip:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_head_color(Color::LightGreen)
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().any(|cell| cell.fg == Color::LightGreen));
+    /// assert!(buf.content().iter().all(|cell| cell.fg != Color::White));
+    /// ```
+    ///
+    /// The color of the tail is [independently configured](Rain::with_color). The
+    /// bold / dim effects that automatically get applied over a drop's length may tweak
+    /// the color inadvertently, but [this can be disabled](Rain::with_bold_dim_effect).
+    pub fn with_head_color(mut self, head_color: Color) -> Rain {
+        self.head_color = head_color;
+        self
+    }
+
+    /// Set whether to apply the bold / dim effect.
+    ///
+    /// By default, the lower third of each drop has the bold effect applied, and the
+    /// upper third has the dim effect applied. This produces an impression of the drop
+    /// fading instead of abruptly ending.
+    ///
+    /// This may tweak the color of glyphs away from the base color on some terminals,
+    /// so it can be disabled if desired:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_bold_dim_effect(false);
+    ///```
+    pub fn with_bold_dim_effect(mut self, bold_dim_effect: bool) -> Rain {
+        self.bold_dim_effect = bold_dim_effect;
+        self
+    }
+
+    /// Set how many rows from the head of each drop are bold, instead of a third.
+    ///
+    /// This gives precise control over the [bold / dim effect](Rain::with_bold_dim_effect)
+    /// for drops of a known length. Here each drop is 10 rows long, with the 2 rows
+    /// nearest the head bold and the 5 rows at the end of the tail
+    /// [dim](Rain::with_dim_rows):
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Modifier};
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// let area = Rect::new(0, 0, 1, 20);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// // The head is at row 15, with the tail reaching up to row 6.
+    /// Rain::new_matrix(Duration::from_secs_f64(5.5))
+    ///     .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    ///     .with_rain_speed_variance(0.0)
+    ///     .with_tail_lifespan(Duration::from_secs(1))
+    ///     .with_bold_rows(2)
+    ///     .with_dim_rows(5)
+    ///     .render_single_drop(vec![0; 40], area, &mut buf);
+    ///
+    /// let modifier = |y| buf[(0, y)].modifier;
+    /// assert!((14..=15).all(|y| modifier(y).contains(Modifier::BOLD)));
+    /// assert!((11..=13).all(|y| modifier(y).is_empty()));
+    /// assert!((6..=10).all(|y| modifier(y).contains(Modifier::DIM)));
+    /// assert!([5, 16].iter().all(|&y| buf[(0, y)].symbol() == " "));
+    /// ```
+    ///
+    /// Rows that are both bold and dim are bold. By default, the third of each drop
+    /// nearest the head is bold.
+    pub fn with_bold_rows(mut self, bold_rows: u16) -> Rain {
+        self.bold_rows = Some(bold_rows);
+        self
+    }
+
+    /// Set how many rows at the end of each drop's tail are dim, instead of a third.
+    ///
+    /// This is the counterpart of [`Rain::with_bold_rows`]:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_dim_rows(5);
+    /// ```
+    ///
+    /// By default, the third of each drop at the end of the tail is dim.
+    pub fn with_dim_rows(mut self, dim_rows: u16) -> Rain {
+        self.dim_rows = Some(dim_rows);
+        self
+    }
+
+    /// Set the interval between random character changes.
+    ///
+    /// A more subtle effect is that glyphs already rendered in a drop occasionally
+    /// switch characters before dissapearing. The time interval between each character
+    /// switch is per-glyph, and can be adjusted:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_noise_interval(Duration::from_secs(10));
+    /// ```
+    pub fn with_noise_interval(mut self, noise_interval: Duration) -> Rain {
+        self.noise_interval = noise_interval;
+        self
+    }
+
+    /// Set the interval between random character changes, in seconds.
+    ///
+    /// This is a convenience for [`Rain::with_noise_interval`]:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_noise_interval_secs(2.5);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is negative, overflows a `Duration`, or isn't finite.
+    pub fn with_noise_interval_secs(self, noise_interval: f64) -> Rain {
+        self.with_noise_interval(Duration::from_secs_f64(noise_interval))
+    }
+
+    /// Set what the noise effect changes.
+    ///
+    /// By default noise switches the character of each glyph. For a twinkling effect,
+    /// noise can instead keep characters stable and cycle their brightness:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{NoiseMode, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_snow(elapsed)
+    ///     .with_noise_mode(NoiseMode::Brightness);
+    /// ```
+    ///
+    /// The brightness cycles at the [noise interval](Rain::with_noise_interval).
+    pub fn with_noise_mode(mut self, noise_mode: NoiseMode) -> Rain {
+        self.noise_mode = noise_mode;
+        self
+    }
+
+    /// Set the character set for the drops.
+    ///
+    /// The simplest option is to provide an explicit set of characters to choose from:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{CharacterSet, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_character_set(CharacterSet::Explicit {
+    ///         options: vec!['a', 'b', 'c'],
+    ///     });
+    /// ```
+    ///
+    /// More performant is to provide a unicode range:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{CharacterSet, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_character_set(CharacterSet::UnicodeRange {
+    ///         start: 0x61,
+    ///         len: 26,
+    ///     });
+    /// ```
+    ///
+    /// Preset unicode ranges include:
+    ///
+    /// - `CharacterSet::HalfKana` is the half-width Japanese kana character set (used
+    ///   in the classic matrix rain)
+    /// - `CharacterSet::Lowercase` is the lowercase English character set
+    ///
+    /// As an escape hatch, any function mapping seeds to characters can be provided:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{CharacterFn, CharacterSet, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_character_set(CharacterSet::Custom {
+    ///         map: CharacterFn::new(|seed| char::from_digit(seed, 10).unwrap()),
+    ///         size: 10,
+    ///     });
+    /// ```
+    pub fn with_character_set(mut self, character_set: CharacterSet) -> Rain {
+        self.character_set = character_set;
+        self
+    }
+
+    /// Set how drops behave at the bottom of the screen.
+    ///
+    /// By default drops fall continuously, so a drop's tail keeps trailing off the
+    /// bottom of the screen after its head has left. Drops can instead be clipped, so
+    /// they vanish as soon as the head leaves the screen:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, WrapMode};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_wrap_mode(WrapMode::Clip);
+    /// ```
+    ///
+    /// Either way, drops respawn at the top of the screen on their next cycle.
+    pub fn with_wrap_mode(mut self, wrap_mode: WrapMode) -> Rain {
+        self.wrap_mode = wrap_mode;
+        self
+    }
+
+    /// Set the aspect ratio of terminal cells, as height divided by width.
+    ///
+    /// Rain speed is measured in cells per second, but terminal cells are usually
+    /// taller than they are wide. To keep the perceived velocity consistent, horizontal
+    /// motion is scaled by this ratio:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_cell_aspect(2.0);
+    /// ```
+    ///
+    /// Vertical motion is unaffected, so this only matters for drops that also move
+    /// horizontally, or speeds given with `RainSpeed::ColumnsPerSecond`. The default of
+    /// `1.0` treats cells as square.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the aspect ratio isn't positive and finite.
+    pub fn with_cell_aspect(mut self, cell_aspect: f64) -> Rain {
+        assert!(
+            cell_aspect > 0.0 && cell_aspect.is_finite(),
+            "cell aspect must be positive and finite"
+        );
+        self.cell_aspect = cell_aspect;
+        self
+    }
+
+    /// Set whether to decorrelate each drop's column and phase.
+    ///
+    /// By default a drop's speed and phase are derived from the same entropy, and so
+    /// are its column and the noise of its glyphs. Occasionally this lines drops up in
+    /// the same column with a similar phase, so they look like a single thick stream.
+    /// Decorrelating derives the column and phase from independently hashed entropy to
+    /// make this less likely:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_decorrelate(true);
+    /// ```
+    ///
+    /// The difference is clearest when the entropy is poorly mixed. Drops whose entropy
+    /// only differs in its high bits all pick the same column, unless decorrelated:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::Rain;
+    ///
+    /// // Count the glyphs in each column over many frames.
+    /// fn column_counts(decorrelate: bool) -> Vec<usize> {
+    ///     let area = Rect::new(0, 0, 64, 32);
+    ///     let mut counts = vec![0; area.width as usize];
+    ///     for frame in 0..20 {
+    ///         let mut buf = Buffer::empty(area);
+    ///         for i in 0..16 {
+    ///             Rain::new_matrix(Duration::from_secs(100 + frame))
+    ///                 .with_decorrelate(decorrelate)
+    ///                 .render_single_drop(vec![i << 58; 48], area, &mut buf);
+    ///         }
+    ///         for (i, cell) in buf.content().iter().enumerate() {
+    ///             if cell.symbol() != " " {
+    ///                 counts[i % area.width as usize] += 1;
+    ///             }
+    ///         }
+    ///     }
+    ///     counts
+    /// }
+    ///
+    /// let (clumped, spread) = (column_counts(false), column_counts(true));
+    /// assert_eq!(clumped.iter().filter(|&&n| n > 0).count(), 1);
+    /// assert!(spread.iter().filter(|&&n| n > 0).count() > 8);
+    /// assert!(spread.iter().max().unwrap() * 2 < *clumped.iter().max().unwrap());
+    /// ```
+    ///
+    /// This changes the pattern generated by a given seed.
+    pub fn with_decorrelate(mut self, decorrelate: bool) -> Rain {
+        self.decorrelate = decorrelate;
+        self
+    }
+
+    /// Set the fraction of each drop's tail to render.
+    ///
+    /// On terminals that don't support the dim effect, the faint end of a tail looks
+    /// like a solid color. Cutting the tail off gives a hard but clean fade instead:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_tail_cutoff(0.6);
+    /// ```
+    ///
+    /// Glyphs beyond this fraction of the drop's length (measured from the head) are not
+    /// rendered. The default of `1.0` renders the whole drop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fraction isn't within `(0, 1]`, since a cutoff of `0` would hide the
+    /// head too.
+    pub fn with_tail_cutoff(mut self, tail_cutoff: f64) -> Rain {
+        assert!(
+            tail_cutoff > 0.0 && tail_cutoff <= 1.0,
+            "tail cutoff must be within (0, 1]"
+        );
+        self.tail_cutoff = tail_cutoff;
+        self
+    }
+
+    /// Set a time window outside of which no drops spawn.
+    ///
+    /// This produces a one-shot burst of rain that starts, runs, and stops, which is
+    /// useful for transitions and intros:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_rain(elapsed)
+    ///     .with_active_window(Duration::from_secs(1), Duration::from_secs(4));
+    /// ```
+    ///
+    /// Nothing is rendered before the window starts, and drops enter from the top of
+    /// the screen once it does. The rain ramps up over the first tenth of the window, and
+    /// back down over the last tenth, by linearly thinning out the drops that spawn. When
+    /// the window ends, drops already falling finish their fall but no new ones spawn, so
+    /// the frame is empty shortly after.
+    pub fn with_active_window(mut self, start: Duration, end: Duration) -> Rain {
+        self.active_window = Some((start, end));
+        self
+    }
+
+    /// Set explicit color stops along the length of each drop.
+    ///
+    /// Each stop is a position along the drop from `0.0` (the head) to `1.0` (the end
+    /// of the tail), and the color at that position. Glyphs between stops are colored
+    /// by interpolating between the nearest stops:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_color_stops(vec![
+    ///         (0.0, Color::White),
+    ///         (0.2, Color::Rgb(0, 255, 0)),
+    ///         (1.0, Color::Rgb(0, 64, 0)),
+    ///     ]);
+    /// ```
+    ///
+    /// When set, this overrides the [color](Rain::with_color) and
+    /// [head color](Rain::with_head_color). Named and indexed colors are converted to
+    /// RGB for interpolation, and colors without an RGB equivalent (like `Color::Reset`)
+    /// snap to the nearest stop instead. An empty list disables the color stops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the positions aren't sorted in ascending order, or are outside `[0, 1]`.
+    pub fn with_color_stops(mut self, color_stops: Vec<(f64, Color)>) -> Rain {
+        assert!(
+            color_stops.iter().all(|(pos, _)| (0.0..=1.0).contains(pos)),
+            "color stop positions must be within [0, 1]"
+        );
+        assert!(
+            color_stops.windows(2).all(|w| w[0].0 <= w[1].0),
+            "color stop positions must be sorted"
+        );
+        self.color_stops = color_stops;
+        self
+    }
+
+    /// Set a color gradient fading along each drop, from the head to the end of the tail.
+    ///
+    /// This is a convenience for [`Rain::with_color_stops`] with a stop at each end:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_tail_gradient(Color::White, Color::Rgb(0, 64, 0))
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().any(|cell| matches!(cell.fg, Color::Rgb(..))));
+    /// assert!(buf.content().iter().all(|cell| cell.fg != Color::LightGreen));
+    /// ```
+    ///
+    /// Each glyph's color is interpolated in RGB by its offset from the head over the
+    /// length of the drop. Here a drop 10 glyphs long is halfway between the colors 5 rows
+    /// above its head:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// let area = Rect::new(0, 0, 1, 20);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// // At 10 rows per second with a 1 second tail, the drop is 10 glyphs long.
+    /// Rain::new_matrix(Duration::from_secs_f64(100.9))
+    ///     .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    ///     .with_rain_speed_variance(0.0)
+    ///     .with_tail_lifespan_secs(1.0)
+    ///     .with_tail_gradient(Color::White, Color::Rgb(0, 64, 0))
+    ///     .render_single_drop(vec![0; 20], area, &mut buf);
+    ///
+    /// let drawn: Vec<u16> = (0..20).filter(|&y| buf[(0, y)].symbol() != " ").collect();
+    /// assert_eq!(drawn.len(), 10);
+    ///
+    /// let head = drawn[9];
+    /// assert_eq!(buf[(0, head)].fg, Color::White);
+    /// assert_eq!(buf[(0, head - 5)].fg, Color::Rgb(128, 160, 128));
+    /// ```
+    ///
+    /// Named and indexed colors are converted to RGB first.
+    pub fn with_tail_gradient(self, head: Color, tail: Color) -> Rain {
+        self.with_color_stops(vec![(0.0, head), (1.0, tail)])
+    }
+
+    /// Set whether to prevent drops from overlapping in the same column.
+    ///
+    /// By default each drop picks a random column every cycle, so two drops can
+    /// occasionally overlap and garble each other. Preventing overlap instead assigns
+    /// each drop its own unique column, while keeping random speeds and phases:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_no_overlap(true);
+    /// ```
+    ///
+    /// Since there is only one drop per column, at most `width` drops are rendered and
+    /// any extra drops from the density are ignored.
+    pub fn with_no_overlap(mut self, no_overlap: bool) -> Rain {
+        self.no_overlap = no_overlap;
+        self
+    }
+
+    /// Set a gradient along each drop between two indexed 256 colors.
+    ///
+    /// Terminals limited to 256 colors approximate RGB colors unpredictably. An indexed
+    /// gradient instead walks the 256 color palette from the head to the tail, so fades
+    /// are predictable:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_indexed_gradient(46, 22);
+    /// ```
+    ///
+    /// When both indices are in the 6x6x6 color cube (16-231), the gradient walks the
+    /// cube. When both are in the grayscale ramp (232-255), it walks the ramp. Otherwise
+    /// the gradient is interpolated in RGB and mapped to the nearest indexed color.
+    ///
+    /// When set, this overrides the [color](Rain::with_color) and
+    /// [head color](Rain::with_head_color), but not [color stops](Rain::with_color_stops).
+    pub fn with_indexed_gradient(mut self, start: u8, end: u8) -> Rain {
+        self.indexed_gradient = Some((start, end));
+        self
+    }
+
+    /// Set the wind angle for the rain, in degrees from vertical.
+    ///
+    /// Wind slants the drops as they fall. Positive angles blow the rain to the right,
+    /// and negative angles to the left:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_rain(elapsed)
+    ///     .with_wind_angle(-20.0);
+    /// ```
+    ///
+    /// Each row a glyph falls moves it `tan(angle)` columns, so at 45 degrees a drop moves
+    /// one column per row. Rendering a drop that fills a whole column shows its slant:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::{Rain, RainSpeed};
+    ///
+    /// // Find the column of the drop on each row.
+    /// fn columns(wind_angle: f64) -> Vec<u16> {
+    ///     let area = Rect::new(0, 0, 80, 80);
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_secs(100))
+    ///         .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    ///         .with_rain_speed_variance(0.0)
+    ///         .with_tail_lifespan_secs(100.0)
+    ///         .with_column_drift(0)
+    ///         .with_wind_angle(wind_angle)
+    ///         .render_single_drop(vec![u64::MAX / 2; 80], area, &mut buf);
+    ///     (0..area.height)
+    ///         .map(|y| (0..area.width).find(|&x| buf[(x, y)].symbol() != " ").unwrap())
+    ///         .collect()
+    /// }
+    ///
+    /// let (right, left) = (columns(45.0), columns(-45.0));
+    /// for y in 1..80 {
+    ///     assert_eq!((right[y] + 80 - right[y - 1]) % 80, 1);
+    ///     assert_eq!((left[y - 1] + 80 - left[y]) % 80, 1);
+    /// }
+    /// ```
+    ///
+    /// Fractional angles are supported for barely-perceptible slants. At half a degree, a
+    /// drop first leans one column over on row 58, since `58 * tan(0.5°)` rounds to 1:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use ratatui::{buffer::Buffer, layout::Rect};
+    /// # use tui_rain::{Rain, RainSpeed};
+    /// # fn columns(wind_angle: f64) -> Vec<u16> {
+    /// #     let area = Rect::new(0, 0, 80, 80);
+    /// #     let mut buf = Buffer::empty(area);
+    /// #     Rain::new_matrix(Duration::from_secs(100))
+    /// #         .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    /// #         .with_rain_speed_variance(0.0)
+    /// #         .with_tail_lifespan_secs(100.0)
+    /// #         .with_column_drift(0)
+    /// #         .with_wind_angle(wind_angle)
+    /// #         .render_single_drop(vec![u64::MAX / 2; 80], area, &mut buf);
+    /// #     (0..area.height)
+    /// #         .map(|y| (0..area.width).find(|&x| buf[(x, y)].symbol() != " ").unwrap())
+    /// #         .collect()
+    /// # }
+    /// let (right, left) = (columns(0.5), columns(-0.5));
+    /// assert!(right[..58].iter().all(|&x| x == right[0]));
+    /// assert!(left[..58].iter().all(|&x| x == left[0]));
+    /// assert_eq!(right[58], right[0] + 1);
+    /// assert_eq!(left[58], left[0] - 1);
+    /// ```
+    ///
+    /// The angle is clamped to `[-80, 80]`, so steeper angles slant like 80 degrees:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use ratatui::{buffer::Buffer, layout::Rect};
+    /// # use tui_rain::{Rain, RainSpeed};
+    /// # fn columns(wind_angle: f64) -> Vec<u16> {
+    /// #     let area = Rect::new(0, 0, 80, 80);
+    /// #     let mut buf = Buffer::empty(area);
+    /// #     Rain::new_matrix(Duration::from_secs(100))
+    /// #         .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    /// #         .with_rain_speed_variance(0.0)
+    /// #         .with_tail_lifespan_secs(100.0)
+    /// #         .with_column_drift(0)
+    /// #         .with_wind_angle(wind_angle)
+    /// #         .render_single_drop(vec![u64::MAX / 2; 80], area, &mut buf);
+    /// #     (0..area.height)
+    /// #         .map(|y| (0..area.width).find(|&x| buf[(x, y)].symbol() != " ").unwrap())
+    /// #         .collect()
+    /// # }
+    /// assert_eq!(columns(100.0), columns(80.0));
+    /// assert_eq!(columns(-100.0), columns(-80.0));
+    /// assert_ne!(columns(80.0), columns(45.0));
+    /// ```
+    ///
+    /// Drops that blow off one side of the screen wrap around to the other. The
+    /// horizontal motion is scaled by the [cell aspect](Rain::with_cell_aspect).
+    pub fn with_wind_angle(mut self, wind_angle: f64) -> Rain {
+        self.wind_angle = wind_angle;
+        self
+    }
+
+    /// Set a rhythmic pulse in the speed of the rain.
+    ///
+    /// The speed of every drop is multiplied by `1 + amplitude * sin(2π * t / period)`,
+    /// so the whole field subtly speeds up and slows down like a heartbeat:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_speed_pulse(0.3, Duration::from_secs(2));
+    /// ```
+    ///
+    /// The amplitude is clamped to `[0, 1]` so drops never move backwards. Only the
+    /// motion of drops pulses, and the noise keeps a steady interval.
+    pub fn with_speed_pulse(mut self, amplitude: f64, period: Duration) -> Rain {
+        self.speed_pulse = Some((amplitude, period));
+        self
+    }
+
+    /// Set the random number generator algorithm for the generation.
+    ///
+    /// Different generators produce different patterns from the same seed, which can
+    /// change the statistical "feel" of drop placement:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RngKind};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rng_kind(RngKind::ChaCha8);
+    /// ```
+    ///
+    /// Every option is portable and reproducible from the seed. The default is
+    /// `RngKind::PcgFast`.
+    pub fn with_rng_kind(mut self, rng_kind: RngKind) -> Rain {
+        self.rng_kind = rng_kind;
+        self
+    }
+
+    /// Set the range of each drop's track length, as multiples of the screen height.
+    ///
+    /// Each drop falls along a track that's longer than the screen, and is hidden while
+    /// it's in the part below the screen. The track lengths are uniformly distributed
+    /// within this range, which controls how cyclic the rain looks:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_track_length_range(2.0, 4.0);
+    /// ```
+    ///
+    /// Larger multiples reduce apparent repetition at a memory and performance cost,
+    /// and leave longer gaps between a drop's cycles. The default is `1.5` to `2.5`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_mult` is greater than `max_mult`, or either is not above `1.0`.
+    pub fn with_track_length_range(mut self, min_mult: f64, max_mult: f64) -> Rain {
+        assert!(min_mult <= max_mult, "min_mult must not exceed max_mult");
+        assert!(min_mult > 1.0, "track length multiples must be above 1.0");
+        self.track_length_range = (min_mult, max_mult);
+        self
+    }
+
+    /// Set whether to guarantee drop heads enter the screen from the top row.
+    ///
+    /// Heads start each cycle at the top row, but they can still first appear further
+    /// down the screen. This happens when the first frame is rendered part way into the
+    /// animation, or when a fast head moves more than one row between frames. This
+    /// option suppresses drops that were already falling when rendering began, and
+    /// draws each newly spawned head on the top row for its first frame:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::StatefulWidget};
+    /// use tui_rain::{Rain, RainDensity, RainSpeed, RainState};
+    ///
+    /// let area = Rect::new(0, 0, 40, 20);
+    /// let mut state = RainState::default();
+    /// let mut previous_heads: Vec<(u16, u16)> = vec![];
+    ///
+    /// // Render fast drops at 4 FPS, so heads move 5 rows per frame.
+    /// for frame in 0..100 {
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_millis(frame * 250))
+    ///         .with_rain_speed(RainSpeed::Absolute { speed: 20.0 })
+    ///         .with_rain_density(RainDensity::Absolute { num_drops: 3 })
+    ///         .with_tail_lifespan(Duration::from_millis(60))
+    ///         .with_head_color(Color::Red)
+    ///         .with_top_entry_only(true)
+    ///         .render(area, &mut buf, &mut state);
+    ///
+    ///     let heads: Vec<(u16, u16)> = area
+    ///         .positions()
+    ///         .filter(|p| buf[*p].fg == Color::Red)
+    ///         .map(|p| (p.x, p.y))
+    ///         .collect();
+    ///
+    ///     // Every head without one above it in the previous frame must be on the top row.
+    ///     for (x, y) in &heads {
+    ///         if !previous_heads.iter().any(|(px, py)| px == x && py < y) {
+    ///             assert_eq!(*y, 0);
+    ///         }
+    ///     }
+    ///     previous_heads = heads;
+    /// }
+    /// ```
+    ///
+    /// Knowing when a head spawned since the previous frame requires rendering with a
+    /// [`RainState`]. Without one, this only suppresses drops that were already falling
+    /// at an elapsed duration of zero (which is also the default behavior).
+    pub fn with_top_entry_only(mut self, top_entry_only: bool) -> Rain {
+        self.top_entry_only = top_entry_only;
+        self
+    }
+
+    /// Set a ramp over which the rain gradually winds down to nothing.
+    ///
+    /// Starting at `start`, the number of drops is scaled down from full to zero over
+    /// `duration`. This is useful for gracefully stopping the rain, such as when closing
+    /// a screen:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_rain(elapsed)
+    ///     .with_despawn_ramp(Duration::from_secs(3), Duration::from_secs(2));
+    /// ```
+    ///
+    /// Drops thin out rather than vanishing instantly. Each drop stops spawning once the
+    /// ramp passes it, but finishes the fall it's already on.
+    pub fn with_despawn_ramp(mut self, start: Duration, duration: Duration) -> Rain {
+        self.despawn_ramp = Some((start, duration));
+        self
+    }
+
+    /// Set the precision drop heads are rendered at within a cell.
+    ///
+    /// By default heads move a whole cell at a time, which looks choppy for slow rain.
+    /// Braille characters have 4 rows of dots per cell, so rendering heads as braille
+    /// smooths their vertical motion:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, SubCell};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_snow(elapsed)
+    ///     .with_subcell(SubCell::Braille);
+    /// ```
+    ///
+    /// Only the head of each drop is affected, and it replaces the head's character.
+    pub fn with_subcell(mut self, subcell: SubCell) -> Rain {
+        self.subcell = subcell;
+        self
+    }
+
+    /// Set the maximum delay before each drop starts falling.
+    ///
+    /// By default each drop starts falling as soon as its first cycle begins, so the
+    /// screen fills quickly. With a start delay, each drop additionally waits a stable
+    /// random delay up to this maximum before its first fall, so it fills gradually:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_start_delay_max(Duration::from_secs(10));
+    /// ```
+    ///
+    /// Delayed drops still enter from the top of the screen.
+    pub fn with_start_delay_max(mut self, start_delay_max: Duration) -> Rain {
+        self.start_delay_max = start_delay_max;
+        self
+    }
+
+    /// Set which glyph is drawn on top when glyphs overlap.
+    ///
+    /// By default the newest glyphs are drawn on top, so drop heads are always visible.
+    /// Layered setups can instead draw the brightest glyphs on top, or supply their own
+    /// comparator:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Priority, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_glyph_priority(Priority::Brightest);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_glyph_priority(Priority::Custom(|a, b| a.x.cmp(&b.x)));
+    /// ```
+    pub fn with_glyph_priority(mut self, glyph_priority: Priority) -> Rain {
+        self.glyph_priority = glyph_priority;
+        self
+    }
+
+    /// Set what drives the noise effect forward.
+    ///
+    /// By default noise advances with the elapsed time, so glyphs everywhere shimmer at
+    /// a steady pace. Noise can instead advance with how far each drop is through its
+    /// current cycle, for a more drop-local shimmer:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{NoiseDriver, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_noise_driver(NoiseDriver::Progress);
+    /// ```
+    pub fn with_noise_driver(mut self, noise_driver: NoiseDriver) -> Rain {
+        self.noise_driver = noise_driver;
+        self
+    }
+
+    /// Set a hard cap on the number of drops, regardless of the density.
+    ///
+    /// This bounds the rendering cost on very large terminals, where a relative density
+    /// would otherwise grow with the screen:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// let rain = Rain::new_matrix(elapsed).with_max_drops(50);
+    ///
+    /// assert_eq!(rain.preview(Rect::new(0, 0, 500, 200)).num_drops, 50);
+    /// ```
+    ///
+    /// The cap applies after the [density](Rain::with_rain_density) has been turned into a
+    /// number of drops for the area, so any density preset gives at most this many drops,
+    /// and a density that gives fewer is unaffected. It also applies after the
+    /// [minimum](Rain::with_min_drops), so a maximum below the minimum wins:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let rain = Rain::new_matrix(Duration::from_secs(5)).with_max_drops(50);
+    ///
+    /// // A dense rain gives 96 drops here, and a sparse one gives 19.
+    /// let dense = rain.clone().with_rain_density(RainDensity::Dense);
+    /// let sparse = rain.clone().with_rain_density(RainDensity::Sparse);
+    /// assert_eq!(dense.preview(area).num_drops, 50);
+    /// assert_eq!(sparse.preview(area).num_drops, 19);
+    ///
+    /// assert_eq!(rain.with_min_drops(60).preview(area).num_drops, 50);
+    /// ```
+    ///
+    /// With a [storm cycle](Rain::with_storm_cycle), the cap applies to the peak of the
+    /// cycle. The calmer part of the cycle is thinned out from the capped number of drops
+    /// in proportion to the calm and storm densities, so a cycle keeps its shape.
+    pub fn with_max_drops(mut self, max_drops: usize) -> Rain {
+        self.max_drops = Some(max_drops);
+        self
+    }
+
+    /// Set a minimum number of drops, regardless of the density.
+    ///
+    /// A relative density can give no drops at all in a small area, which looks broken in
+    /// a little panel. A minimum makes sure there's always some rain:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// let rain = Rain::new_matrix(elapsed).with_min_drops(3);
+    ///
+    /// assert_eq!(rain.preview(Rect::new(0, 0, 10, 4)).num_drops, 3);
+    /// ```
+    ///
+    /// The minimum is capped at one drop per column of the area, so it doesn't overwhelm
+    /// truly tiny areas. A [maximum](Rain::with_max_drops) takes precedence over it.
+    pub fn with_min_drops(mut self, min_drops: usize) -> Rain {
+        self.min_drops = min_drops;
+        self
+    }
+
+    /// Set how opaque the rain is over the existing contents of the buffer.
+    ///
+    /// By default (`1.0`) glyphs replace the style of the cells they're drawn over. Lower
+    /// values blend each glyph's color with the existing foreground color of its cell,
+    /// for a translucent overlay (e.g. over a dimmed snapshot of your UI):
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_blend(0.5);
+    /// ```
+    ///
+    /// Colors without an RGB equivalent (like `Color::Reset`) can't be blended, and snap to
+    /// whichever color is nearer instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the alpha is not within [0, 1].
+    pub fn with_blend(mut self, alpha: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&alpha),
+            "blend alpha must be within [0, 1]"
+        );
+        self.blend = alpha;
+        self
+    }
+
+    /// Set a floor on the brightness of glyphs colored by color stops.
+    ///
+    /// On washed-out or low-contrast displays, glyphs toward the dark end of a gradient
+    /// can disappear into the background. Colors darker than the given luminance (within
+    /// [0, 1]) are brightened toward white until they reach it:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_color_stops(vec![(0.0, Color::White), (1.0, Color::Black)])
+    ///     .with_min_brightness(0.2);
+    /// ```
+    ///
+    /// The default of `0.0` leaves colors unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the brightness is not within [0, 1].
+    pub fn with_min_brightness(mut self, min_brightness: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&min_brightness),
+            "minimum brightness must be within [0, 1]"
+        );
+        self.min_brightness = min_brightness;
+        self
+    }
+
+    /// Set how many cells wide and tall each pixel of rain is.
+    ///
+    /// The rain is computed on a grid scaled down by this factor, and each glyph is drawn
+    /// as an n×n block of cells, for a chunky retro look:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_pixel_scale(2);
+    /// ```
+    ///
+    /// When the area isn't a multiple of the scale, the blocks along the right and bottom
+    /// edges are cut off at the edge of the area. The density is computed from the
+    /// scaled grid, so the same density looks similarly full at any scale.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scale is 0.
+    pub fn with_pixel_scale(mut self, scale: u16) -> Rain {
+        assert!(scale > 0, "pixel scale must be at least 1");
+        self.pixel_scale = scale;
+        self
+    }
+
+    /// Set whether each cell keeps the same character for the whole time a drop passes.
+    ///
+    /// By default each glyph's character cycles with the noise. With falling glyphs, the
+    /// character is instead fixed by the cell and the drop's cycle, so each fall traces
+    /// out a stable column of characters that only changes on the next fall:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_falling_glyphs(true);
+    /// ```
+    ///
+    /// Cells then keep their characters from frame to frame, even with fast noise:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let render = |millis| {
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_millis(millis))
+    ///         .with_noise_interval(Duration::from_millis(1))
+    ///         .with_falling_glyphs(true)
+    ///         .render(area, &mut buf);
+    ///     buf
+    /// };
+    ///
+    /// let (before, after) = (render(5000), render(5050));
+    /// for (before, after) in before.content.iter().zip(&after.content) {
+    ///     let (before, after) = (before.symbol(), after.symbol());
+    ///     assert!(before == " " || after == " " || before == after);
+    /// }
+    /// ```
+    ///
+    /// The noise interval still applies to brightness noise.
+    pub fn with_falling_glyphs(mut self, falling_glyphs: bool) -> Rain {
+        self.falling_glyphs = falling_glyphs;
+        self
+    }
+
+    /// Leave faint ghosts of glyphs behind in cells that drops recently passed through.
+    ///
+    /// Each ghost starts in the given color and fades toward black over the decay, for a
+    /// lingering phosphor-burn look. Remembering which cells drops passed through needs
+    /// persistent state, so ghosts are only drawn when rendering as a [`StatefulWidget`]:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::StatefulWidget};
+    /// use tui_rain::{Rain, RainState};
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// let mut state = RainState::default();
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_ghost_trail(Duration::from_secs(1), Color::Green)
+    ///     .render(area, &mut buf, &mut state);
+    /// ```
+    pub fn with_ghost_trail(mut self, decay: Duration, color: Color) -> Rain {
+        self.ghost_trail = Some((decay, color));
+        self
+    }
+
+    /// Set a factor to speed up or slow down the whole animation.
+    ///
+    /// The elapsed duration is multiplied by the time scale when rendering, so speed,
+    /// tails, noise, and everything else derived from the elapsed duration change
+    /// together. At `0.5` the rain runs at half speed, and at `2.0` it runs at double:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_time_scale(0.5);
+    /// ```
+    ///
+    /// So a half-speed rain after 10 seconds looks the same as a normal rain after 5:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let (mut slow, mut normal) = (Buffer::empty(area), Buffer::empty(area));
+    ///
+    /// Rain::new_matrix(Duration::from_secs(10))
+    ///     .with_time_scale(0.5)
+    ///     .render(area, &mut slow);
+    /// Rain::new_matrix(Duration::from_secs(5)).render(area, &mut normal);
+    ///
+    /// assert_eq!(slow, normal);
+    /// ```
+    ///
+    /// The scale applies to whichever elapsed duration is set when rendering, including
+    /// one set later with [`Rain::with_elapsed`]. Changing the scale mid-animation jumps
+    /// to the scaled time, so ease between scales by accumulating scaled time yourself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time scale is negative or not finite.
+    pub fn with_time_scale(mut self, time_scale: f64) -> Rain {
+        assert!(
+            time_scale.is_finite() && time_scale >= 0.0,
+            "time scale must be finite and non-negative"
+        );
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Set characters that are never drawn, leaving their cells untouched.
+    ///
+    /// Glyphs with any of these characters are skipped when writing to the buffer, so
+    /// whatever was underneath shows through. With an explicit character set, this can
+    /// sculpt sparse patterns within dense drops:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{CharacterSet, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_character_set(CharacterSet::Explicit {
+    ///         options: vec!['1', '0', ' '],
+    ///     })
+    ///     .with_transparent_chars(vec![' ']);
+    /// ```
+    pub fn with_transparent_chars(mut self, transparent_chars: Vec<char>) -> Rain {
+        self.transparent_chars = transparent_chars;
+        self
+    }
+
+    /// Set whether to write glyphs to the buffer in scanline order.
+    ///
+    /// Glyphs are normally written in priority order, with higher priority glyphs drawn
+    /// over lower ones. With scanline output, only the glyph that would end up on top of
+    /// each cell is written, top-to-bottom and left-to-right. Some terminal backends and
+    /// slow remote terminals flush more efficiently this way:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let (mut scanline, mut normal) = (Buffer::empty(area), Buffer::empty(area));
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_scanline_output(true)
+    ///     .render(area, &mut scanline);
+    /// Rain::new_matrix(Duration::from_secs(5)).render(area, &mut normal);
+    ///
+    /// assert_eq!(scanline, normal);
+    /// ```
+    ///
+    /// This doesn't change the result, except when blending: glyphs then only blend with
+    /// the existing contents of the buffer, and not with the glyphs beneath them.
+    pub fn with_scanline_output(mut self, scanline_output: bool) -> Rain {
+        self.scanline_output = scanline_output;
+        self
+    }
+
+    /// Set whether to remove the bias toward low columns when choosing drop columns.
+    ///
+    /// Columns are chosen by taking random entropy modulo the width, which very slightly
+    /// over-represents the leftmost columns for some widths. Uniform columns instead use
+    /// a multiply-shift with rejection, so every column is exactly equally likely:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 7, 10);
+    /// let mut counts = [0.0; 7];
+    ///
+    /// // Count the columns of many drops' (white) heads.
+    /// let mut seed = 1u64;
+    /// for _ in 0..1400 {
+    ///     let entropy = (0..20)
+    ///         .map(|_| {
+    ///             seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+    ///             seed
+    ///         })
+    ///         .collect();
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_secs(60))
+    ///         .with_uniform_columns(true)
+    ///         .render_single_drop(entropy, area, &mut buf);
+    ///     for x in 0..area.width {
+    ///         if (0..area.height).any(|y| buf[(x, y)].fg == Color::White) {
+    ///             counts[x as usize] += 1.0;
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// // The chi-square statistic is well under the critical value for 6 degrees of freedom.
+    /// let expected = counts.iter().sum::<f64>() / counts.len() as f64;
+    /// let chi_square: f64 = counts.iter().map(|c| (c - expected).powi(2) / expected).sum();
+    /// assert!(chi_square < 22.46);
+    /// ```
+    ///
+    /// The bias is tiny for typical widths, so this only matters statistically, and it
+    /// changes which columns drops fall in.
+    pub fn with_uniform_columns(mut self, uniform_columns: bool) -> Rain {
+        self.uniform_columns = uniform_columns;
+        self
+    }
+
+    /// Set how the speeds of individual drops are distributed around the target speed.
+    ///
+    /// By default speeds are uniformly distributed within the rain speed variance. A
+    /// normal distribution keeps most drops near the target speed with a few outliers,
+    /// for more natural-looking variety:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, SpeedDistribution};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_speed_distribution(SpeedDistribution::Normal { stddev: 0.2 });
+    /// ```
+    ///
+    /// An exponential distribution has mostly slow drops and a few fast ones. Either way,
+    /// the speed of an individual drop will never go below 0.001 rows / second.
+    pub fn with_speed_distribution(mut self, speed_distribution: SpeedDistribution) -> Rain {
+        self.speed_distribution = speed_distribution;
+        self
+    }
+
+    /// Set how often bright sparkles detach from drop tails, and their color.
+    ///
+    /// The rate is the chance (within [0, 1]) that each tail glyph throws off a sparkle
+    /// into a neighboring column. Each sparkle lasts only until the drop moves another
+    /// row, for a magical glittering effect:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_sparkle(0.05, Color::LightYellow);
+    /// ```
+    ///
+    /// Sparkles are bold, and have the same age as the glyph they detached from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not within [0, 1].
+    pub fn with_sparkle(mut self, rate: f64, color: Color) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&rate),
+            "sparkle rate must be within [0, 1]"
+        );
+        self.sparkle = Some((rate, color));
+        self
+    }
+
+    /// Mirror the rain across the center of the area, for a kaleidoscope effect.
+    ///
+    /// Rain is only computed for half of the area, and reflected onto the other half.
+    /// Mirroring across the vertical axis reflects the left half onto the right:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::{Axis, Rain};
+    ///
+    /// let area = Rect::new(0, 0, 21, 10);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_mirror(Axis::Vertical)
+    ///     .render(area, &mut buf);
+    ///
+    /// for y in 0..area.height {
+    ///     for x in 0..area.width {
+    ///         assert_eq!(buf[(x, y)], buf[(area.width - 1 - x, y)]);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Mirroring across the horizontal axis reflects the top half onto the bottom, so
+    /// drops appear to rise from the bottom edge too. With an odd size, the center column
+    /// or row belongs to the computed half and is its own reflection.
+    pub fn with_mirror(mut self, axis: Axis) -> Rain {
+        self.mirror = Some(axis);
+        self
+    }
+
+    /// Set a duration to shift the animation by.
+    ///
+    /// The offset is added to the elapsed duration when rendering. Unlike changing the
+    /// seed, this shows the same pattern, just further along in time. This keeps several
+    /// rain widgets from pulsing together, while they still share one clock:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let (mut shifted, mut later) = (Buffer::empty(area), Buffer::empty(area));
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_time_offset(Duration::from_secs(3))
+    ///     .render(area, &mut shifted);
+    /// Rain::new_matrix(Duration::from_secs(8)).render(area, &mut later);
+    ///
+    /// assert_eq!(shifted, later);
+    /// ```
+    ///
+    /// The offset is added before any time scale is applied.
+    pub fn with_time_offset(mut self, time_offset: Duration) -> Rain {
+        self.time_offset = time_offset;
+        self
+    }
+
+    /// Set whether to render only the head of each drop, without its tail.
+    ///
+    /// This gives a minimalist look of sparse falling dots, and greatly reduces the
+    /// number of glyphs. Combine it with a low density for a gentle effect:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rain_density(RainDensity::Sparse)
+    ///     .with_heads_only(true);
+    /// ```
+    pub fn with_heads_only(mut self, heads_only: bool) -> Rain {
+        self.heads_only = heads_only;
+        self
+    }
+
+    /// Set how the brightness of glyphs is styled.
+    ///
+    /// By default glyphs are made bold or dim to vary their brightness. Some terminals
+    /// also change the color of bold text, which can fight color gradients. Styling by
+    /// color only never makes glyphs bold or dim, leaving the brightness falloff to the
+    /// colors:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::{Rain, StyleMode};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_color_stops(vec![(0.0, Color::White), (1.0, Color::Rgb(0, 64, 0))])
+    ///     .with_style_mode(StyleMode::ColorOnly);
+    /// ```
+    pub fn with_style_mode(mut self, style_mode: StyleMode) -> Rain {
+        self.style_mode = style_mode;
+        self
+    }
+
+    /// Cycle the rain between calm and stormy periods.
+    ///
+    /// Over each period, the density eases from the calm density up to the storm density
+    /// and back again, following a sine wave driven by the elapsed duration. This makes
+    /// for evolving weather in an ambient background:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_rain(elapsed)
+    ///     .with_storm_cycle(Duration::from_secs(60), RainDensity::Drizzle, RainDensity::Dense);
+    /// ```
+    ///
+    /// This replaces the rain density. Like the despawn ramp, drops are thinned out
+    /// without reshuffling the pattern, and the change applies as drops begin new falls.
+    pub fn with_storm_cycle(
+        mut self,
+        period: Duration,
+        calm_density: RainDensity,
+        storm_density: RainDensity,
+    ) -> Rain {
+        self.storm_cycle = Some((period, calm_density, storm_density));
+        self
+    }
+
+    /// Set a function choosing the character set for each row, overriding the character set.
+    ///
+    /// This allows layered textures, such as sparse dots near the top of the screen and
+    /// dense blocks near the bottom:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{CharacterSet, Rain};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_character_set_by_row(|y| {
+    ///         if y < 10 {
+    ///             CharacterSet::Explicit { options: vec!['.', '·'] }
+    ///         } else {
+    ///             CharacterSet::Explicit { options: vec!['▒', '▓'] }
+    ///         }
+    ///     });
+    /// ```
+    ///
+    /// The function is called with rows relative to the area, once per glyph, so it should
+    /// be cheap. For the common case, prefer [`Rain::with_character_set`].
+    pub fn with_character_set_by_row(
+        mut self,
+        by_row: impl Fn(u16) -> CharacterSet + Send + Sync + 'static,
+    ) -> Rain {
+        self.character_set_by_row = Some(CharacterSetFn::new(by_row));
+        self
+    }
+
+    /// Set a minimum interval between recomputing frames, when rendering with a state.
+    ///
+    /// If the rain is rendered again within the interval of the last computed frame, the
+    /// same glyphs are drawn again instead of recomputing them. This caps the CPU cost
+    /// when the app renders much more often than the rain needs:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+    /// use tui_rain::{Rain, RainState};
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut state = RainState::default();
+    /// let rain = |millis| {
+    ///     Rain::new_matrix(Duration::from_millis(millis))
+    ///         .with_min_frame_interval(Duration::from_millis(100))
+    /// };
+    ///
+    /// let (mut first, mut second) = (Buffer::empty(area), Buffer::empty(area));
+    /// rain(5000).render(area, &mut first, &mut state);
+    /// rain(5050).render(area, &mut second, &mut state);
+    ///
+    /// assert_eq!(first, second);
+    /// ```
+    ///
+    /// Frames are always recomputed when the area or any other configuration changes.
+    pub fn with_min_frame_interval(mut self, min_frame_interval: Duration) -> Rain {
+        self.min_frame_interval = Some(min_frame_interval);
+        self
+    }
+
+    /// Set the strength of a vignette, dimming the rain toward the edges of the area.
+    ///
+    /// Each glyph's color is scaled toward black by its distance from the center of the
+    /// area, reaching `1 - strength` brightness in the corners. Dimmed colors are converted to
+    /// RGB, and colors without an RGB equivalent are left unchanged. Defaults to `0.0`, which
+    /// disables the vignette:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 40, 20);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(30))
+    ///     .with_color(Color::Rgb(0, 200, 0))
+    ///     .with_head_color(Color::Rgb(0, 200, 0))
+    ///     .with_vignette(1.0)
+    ///     .render(area, &mut buf);
+    ///
+    /// // Nothing is brighter than the undimmed color, and the edges are dimmed.
+    /// let mut drawn = 0;
+    /// let mut dimmed_edges = 0;
+    /// for (i, cell) in buf.content().iter().enumerate() {
+    ///     if cell.symbol() == " " {
+    ///         continue;
+    ///     }
+    ///     let Color::Rgb(_, green, _) = cell.fg else { panic!() };
+    ///     assert!(green <= 200);
+    ///
+    ///     let (x, y) = buf.pos_of(i);
+    ///     let near_edge = x < 5 || x >= 35 || y < 3 || y >= 17;
+    ///     drawn += 1;
+    ///     dimmed_edges += (near_edge && green < 200) as usize;
+    /// }
+    /// assert!(drawn > 0);
+    /// assert!(dimmed_edges > 0);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `strength` is not between `0.0` and `1.0` inclusive.
+    pub fn with_vignette(mut self, strength: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&strength),
+            "vignette strength must be between 0.0 and 1.0, got {strength}"
+        );
+        self.vignette = strength;
+        self
+    }
+
+    /// Set whether drops bounce once off the bottom of the screen.
+    ///
+    /// When a drop's head reaches the bottom row, it eases back up by a sixth of the
+    /// screen height and falls back down before leaving the screen. The tail follows the
+    /// head's path, so it folds over itself during the bounce:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rain_density(RainDensity::Sparse)
+    ///     .with_bounce(true);
+    /// ```
+    pub fn with_bounce(mut self, bounce: bool) -> Rain {
+        self.bounce = bounce;
+        self
+    }
+
+    /// Set whether to shuffle the order the noise cycles through the character set.
+    ///
+    /// By default, the noise walks through characters in the set's order, which can look
+    /// predictable with a small explicit set. Shuffling walks through the same characters
+    /// in an order permuted by the seed, which stays the same every frame:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::{CharacterSet, Rain};
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_character_set(CharacterSet::Explicit {
+    ///         options: vec!['a', 'b', 'c', 'd', 'e'],
+    ///     })
+    ///     .with_shuffled_charset(true)
+    ///     .render(area, &mut buf);
+    ///
+    /// // The set of characters is unchanged.
+    /// assert!(buf.content().iter().any(|cell| "abcde".contains(cell.symbol())));
+    /// assert!(buf.content().iter().all(|cell| " abcde".contains(cell.symbol())));
+    /// ```
+    pub fn with_shuffled_charset(mut self, shuffled_charset: bool) -> Rain {
+        self.shuffled_charset = shuffled_charset;
+        self
+    }
+
+    /// Set the velocity of the rain directly, in columns and rows per second.
+    ///
+    /// This is an alternative to setting the [speed](Rain::with_rain_speed) and
+    /// [wind angle](Rain::with_wind_angle), and overrides both. The vertical component sets
+    /// how fast drops fall, and the horizontal component how fast they move sideways:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// // Fall 20 rows per second while drifting 5 columns per second to the left.
+    /// Rain::new_rain(elapsed)
+    ///     .with_velocity(-5.0, 20.0);
+    /// ```
+    ///
+    /// Speed variance still applies, scaling both components of a drop's velocity together
+    /// so every drop keeps the same slant. Unlike the wind angle, the horizontal component
+    /// is not scaled by the [cell aspect](Rain::with_cell_aspect).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `vy` is not positive, or if `vx` is not finite.
+    pub fn with_velocity(mut self, vx: f64, vy: f64) -> Rain {
+        assert!(vy > 0.0, "vertical velocity must be positive, got {vy}");
+        assert!(
+            vx.is_finite(),
+            "horizontal velocity must be finite, got {vx}"
+        );
+        self.velocity = Some((vx, vy));
+        self
+    }
+
+    /// Set the most columns a drop can move between falls, rather than jumping anywhere.
+    ///
+    /// By default, each time a drop wraps around to the top it falls in a new random
+    /// column. With column drift, its column instead takes a random walk, moving by at most
+    /// `max` columns each time it wraps, so wrapping drops feel continuous:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_column_drift(2);
+    /// ```
+    ///
+    /// A drift of `0` keeps every drop in a fixed column. Drops that drift off one side of
+    /// the screen wrap around to the other. Column drift has no effect with
+    /// [no overlap](Rain::with_no_overlap), which already fixes each drop's column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is negative.
+    pub fn with_column_drift(mut self, max: i16) -> Rain {
+        assert!(max >= 0, "column drift must not be negative, got {max}");
+        self.column_drift = Some(max);
+        self
+    }
+
+    /// Set a function combining each glyph with the cell it's drawn over.
+    ///
+    /// By default, a glyph replaces the cell's character and patches its style. With a
+    /// cell merge function, the cell is instead replaced by whatever the function returns
+    /// for the existing cell and the glyph. For example, to keep the existing characters
+    /// and only recolor them:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::filled(area, ratatui::buffer::Cell::new("#"));
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_cell_merge(|cell, glyph| {
+    ///         let mut cell = cell.clone();
+    ///         cell.set_style(glyph.style);
+    ///         cell
+    ///     })
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().all(|cell| cell.symbol() == "#"));
+    /// ```
+    ///
+    /// This replaces [blending](Rain::with_blend), which the function can do itself if
+    /// needed. Glyphs with [transparent characters](Rain::with_transparent_chars) are still
+    /// skipped. Since functions can't be compared, rains with cell merge functions are only
+    /// equal if they share the same function, as with [`CellMergeFn`].
+    pub fn with_cell_merge(
+        mut self,
+        merge: impl Fn(&Cell, &Glyph) -> Cell + Send + Sync + 'static,
+    ) -> Rain {
+        self.cell_merge = Some(CellMergeFn::new(merge));
+        self
+    }
+
+    /// Quickly check whether rendering to the given area would certainly draw nothing.
+    ///
+    /// This doesn't build any glyphs, so it's cheap enough to decide whether to render the
+    /// rain at all, e.g. to skip it once a fade-out has finished:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let rain = |secs| {
+    ///     Rain::new_matrix(Duration::from_secs(secs))
+    ///         .with_active_window(Duration::from_secs(10), Duration::from_secs(20))
+    /// };
+    ///
+    /// assert!(rain(5).is_empty(area));
+    /// assert!(!rain(15).is_empty(area));
+    /// assert!(rain(600).is_empty(area));
+    /// ```
+    ///
+    /// This is conservative: a rain that isn't empty by this check may still happen to draw
+    /// nothing. Drops that stopped spawning at the end of the active window or
+    /// [despawn ramp](Rain::with_despawn_ramp) are only considered gone once even the
+    /// slowest possible drop would have left the screen.
+    pub fn is_empty(&self, area: Rect) -> bool {
+        let grid = self.pixel_grid(area);
+        if grid.is_empty() || self.num_drops(grid) == 0 {
+            return true;
+        }
+
+        let elapsed = self.clock_secs(self.elapsed);
+        if let Some((start, _)) = self.active_window {
+            if elapsed < start.as_secs_f64() {
+                return true;
+            }
+        }
+
+        // Find when drops stop spawning for good, if they ever do.
+        let window_end = self.active_window.map(|(_, end)| end);
+        let ramp_end = self.despawn_ramp.map(|(start, duration)| start + duration);
+        let Some(spawn_end) = window_end.into_iter().chain(ramp_end).min() else {
+            return false;
+        };
+
+        // After that, the last cycles can take a full track and tail to finish falling.
+        let min_speed = match self.speed_distribution {
+            SpeedDistribution::Uniform => self.base_speed() * (1.0 - self.rain_speed_variance),
+            _ => 0.0,
+        }
+        .max(1e-3);
+        let (min_track_len, track_len_spread) = self.track_len_bounds(grid.height);
+        let max_track_len = (min_track_len + track_len_spread) as f64;
+        let drain_secs = (max_track_len + grid.height as f64) / min_speed;
+
+        self.motion_secs(elapsed) >= self.motion_secs(spawn_end.as_secs_f64()) + drain_secs
+    }
+
+    /// Set whether to clear the whole area before drawing the rain.
+    ///
+    /// By default, only cells containing glyphs are written, so anything already in the
+    /// buffer shows through between drops. Clearing first resets every cell in the area to
+    /// a blank space with the default style, guaranteeing a clean frame:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{
+    ///     buffer::{Buffer, Cell},
+    ///     layout::Rect,
+    ///     widgets::Widget,
+    /// };
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::filled(area, Cell::new("#"));
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_clear(true)
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().all(|cell| cell.symbol() != "#"));
+    /// ```
+    ///
+    /// This is useful when the rain is drawn over a region that previously held other
+    /// widgets. Leave it off to composite the rain over other content.
+    pub fn with_clear(mut self, clear: bool) -> Rain {
+        self.clear = clear;
+        self
+    }
+
+    /// Set a weighted list of tail lengths for drops to choose from, in rows.
+    ///
+    /// By default, a drop's tail length follows from its speed and the
+    /// [tail lifespan](Rain::with_tail_lifespan). With tail length weights, each drop
+    /// instead picks one of the given lengths at random, in proportion to its weight. For
+    /// example, to make most drops short with the occasional very long one:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_tail_length_weights(vec![(4, 0.8), (8, 0.15), (30, 0.05)]);
+    /// ```
+    ///
+    /// Each drop keeps the same length every frame. Lengths are capped at the height of
+    /// the area. An empty list restores the default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any weight is negative or not finite, or if a non-empty list's weights
+    /// sum to zero.
+    pub fn with_tail_length_weights(mut self, tail_length_weights: Vec<(u16, f64)>) -> Rain {
+        assert!(
+            tail_length_weights
+                .iter()
+                .all(|(_, weight)| weight.is_finite() && *weight >= 0.0),
+            "tail length weights must be finite and non-negative"
+        );
+        assert!(
+            tail_length_weights.is_empty()
+                || tail_length_weights
+                    .iter()
+                    .map(|(_, weight)| weight)
+                    .sum::<f64>()
+                    > 0.0,
+            "tail length weights must not all be zero"
+        );
+        self.tail_length_weights = tail_length_weights;
+        self
+    }
+
+    /// Interpolate between two rain configurations by `t`, e.g. to crossfade between presets.
+    ///
+    /// At `t = 0.0` this is `a`, and at `t = 1.0` it's `b`. In between, these numeric
+    /// fields are interpolated:
+    ///
+    /// - the [density](Rain::with_rain_density), if both are absolute, both are relative, or
+    ///   both are target coverages
+    /// - the [speed](Rain::with_rain_speed), converted to rows per second
+    /// - the [speed variance](Rain::with_rain_speed_variance)
+    /// - the [tail lifespan](Rain::with_tail_lifespan)
+    /// - the [color](Rain::with_color) and [head color](Rain::with_head_color), in RGB
+    ///
+    /// Every other field (including the character set, seed and elapsed duration) snaps
+    /// from `a` to `b` at `t = 0.5`, as do densities that can't be interpolated and colors
+    /// without an RGB equivalent:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    /// let (matrix, snow) = (Rain::new_matrix(elapsed), Rain::new_snow(elapsed));
+    ///
+    /// let halfway = Rain::lerp(&matrix, &snow, 0.5);
+    ///
+    /// assert_ne!(halfway, matrix);
+    /// assert_ne!(halfway, snow);
+    /// assert_eq!(Rain::lerp(&matrix, &snow, 0.0), matrix);
+    /// assert_eq!(Rain::lerp(&matrix, &snow, 1.0), snow);
+    /// ```
+    ///
+    /// Since drop positions depend on their speed, drops may jump around while the speed
+    /// is being interpolated if the transition is fast.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t` is not between `0.0` and `1.0` inclusive.
+    pub fn lerp(a: &Rain, b: &Rain, t: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&t),
+            "t must be between 0.0 and 1.0, got {t}"
+        );
+        if t == 0.0 {
+            return a.clone();
+        } else if t == 1.0 {
+            return b.clone();
+        }
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        let mut rain = if t < 0.5 { a.clone() } else { b.clone() };
+
+        // Densities are interpolated as drops per pixel when relative, to stay linear.
+        rain.rain_density = match (a.rain_density, b.rain_density) {
+            (RainDensity::Absolute { num_drops: a }, RainDensity::Absolute { num_drops: b }) => {
+                RainDensity::Absolute {
+                    num_drops: lerp(a as f64, b as f64).round() as usize,
+                }
+            }
+            (
+                RainDensity::TargetCoverage { fraction: a },
+                RainDensity::TargetCoverage { fraction: b },
+            ) => RainDensity::TargetCoverage {
+                fraction: lerp(a, b),
+            },
+            (a, b) => match (a.drops_per_pixel(), b.drops_per_pixel()) {
+                (Some(a), Some(b)) => {
+                    let drops_per_pixel = lerp(a, b);
+                    RainDensity::Relative {
+                        sparseness: if drops_per_pixel > 0.0 {
+                            ((1.0 / drops_per_pixel).round() as usize).max(1)
+                        } else {
+                            0
+                        },
+                    }
+                }
+                _ => rain.rain_density,
+            },
+        };
+        rain.rain_speed = RainSpeed::RowsPerSecond {
+            speed: lerp(
+                a.rain_speed.speed(a.cell_aspect),
+                b.rain_speed.speed(b.cell_aspect),
+            ),
+        };
+        rain.rain_speed_variance = lerp(a.rain_speed_variance, b.rain_speed_variance);
+        rain.tail_lifespan = Duration::from_secs_f64(lerp(
+            a.tail_lifespan.as_secs_f64(),
+            b.tail_lifespan.as_secs_f64(),
+        ));
+        rain.color = lerp_color(a.color, b.color, t);
+        rain.head_color = lerp_color(a.head_color, b.head_color, t);
+        rain
+    }
+
+    /// Set whether to color each drop by how far it is through its cycle, for debugging.
+    ///
+    /// This overrides every glyph's color with a heatmap of its drop's progress through its
+    /// track, from blue when the drop has just wrapped around to red when it's about to wrap
+    /// again. It's a development aid for tuning track lengths and speeds:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_debug_age_coloring(true);
+    /// ```
+    pub fn with_debug_age_coloring(mut self, debug_age_coloring: bool) -> Rain {
+        self.debug_age_coloring = debug_age_coloring;
+        self
+    }
+
+    /// Set a shadow to draw in the cell just behind each drop's head.
+    ///
+    /// The shadow replaces the first glyph of the tail with the given character and
+    /// color, giving the leading edge of each drop more weight. It's drawn below the head
+    /// but above the rest of the tail:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::style::Color;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_rain(elapsed)
+    ///     .with_head_shadow('╎', Color::Rgb(40, 60, 120));
+    /// ```
+    ///
+    /// The shadow follows the head's path, and is still drawn when rendering
+    /// [only heads](Rain::with_heads_only) or with a short [tail cutoff](Rain::with_tail_cutoff).
+    pub fn with_head_shadow(mut self, shadow: char, color: Color) -> Rain {
+        self.head_shadow = Some((shadow, color));
+        self
+    }
+
+    /// Color the rain from a [`Palette`], from the head to the end of the tail.
+    ///
+    /// The palette's colors become evenly spaced [color stops](Rain::with_color_stops),
+    /// with the first color at the head and the last at the end of the tail:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Palette, Rain};
+    ///
+    /// let palette = Palette::from_hex_list(&["#e0ffe0", "#00ff41", "#008f11", "#003b00"])
+    ///     .expect("palette should be valid");
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_palette(&palette);
+    /// ```
+    ///
+    /// The first color also becomes the [head color](Rain::with_head_color), and the last
+    /// the [color](Rain::with_color), for any effect that uses those. An empty palette
+    /// disables the color stops and leaves the colors unchanged.
+    pub fn with_palette(mut self, palette: &Palette) -> Rain {
+        let colors = &palette.colors;
+        self.color_stops = match colors.len() {
+            0 => vec![],
+            1 => vec![(0.0, colors[0])],
+            len => colors
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as f64 / (len - 1) as f64, *color))
+                .collect(),
+        };
+        if let (Some(first), Some(last)) = (colors.first(), colors.last()) {
+            self.head_color = *first;
+            self.color = *last;
+        }
+        self
+    }
+
+    /// Set a mask of cells the rain may appear in, e.g. to fill the shape of some text.
+    ///
+    /// The mask is a grid of rows relative to the area, where `true` allows rain in that
+    /// cell. Glyphs in any other cell are culled. It can be generated from figlet-style
+    /// ASCII art:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let art = [
+    ///     "#   # ",
+    ///     "## ## ",
+    ///     "# # # ",
+    ///     "#   # ",
+    /// ];
+    /// let mask = art
+    ///     .iter()
+    ///     .map(|row| row.chars().map(|c| c == '#').collect())
+    ///     .collect();
+    ///
+    /// let area = Rect::new(0, 0, 6, 4);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_rain_density(RainDensity::Dense)
+    ///     .with_shape_mask(mask)
+    ///     .render(area, &mut buf);
+    ///
+    /// let mut drawn = false;
+    /// for (i, cell) in buf.content().iter().enumerate() {
+    ///     let (x, y) = buf.pos_of(i);
+    ///     if art[y as usize].as_bytes()[x as usize] == b' ' {
+    ///         assert_eq!(cell.symbol(), " ");
+    ///     } else {
+    ///         drawn |= cell.symbol() != " ";
+    ///     }
+    /// }
+    /// assert!(drawn);
     /// ```
     ///
-    /// The actual number of drops on the screen at any time is randomly distributed
-    /// between 0 and twice the target.
+    /// The mask isn't scaled to the area. Cells beyond the end of a row, or below the last
+    /// row, are treated as `false`, so a mask smaller than the area is padded with empty
+    /// space, and a larger one is clipped to the area.
+    pub fn with_shape_mask(mut self, shape_mask: Vec<Vec<bool>>) -> Rain {
+        self.shape_mask = Some(shape_mask);
+        self
+    }
+
+    /// Set how each drop's head position is rounded to a row.
     ///
-    /// Preset relative options include:
+    /// By default, the head's position is rounded down, so it only reaches a row once
+    /// it's fully there. On slow rain this makes the head linger in a row then jump to the
+    /// next. Rounding to the nearest row instead centers the head's motion on each row:
     ///
-    /// - `RainDensity::Sparse`
-    /// - `RainDensity::Normal`
-    /// - `RainDensity::Dense`
-    pub fn with_rain_density(mut self, rain_density: RainDensity) -> Rain {
-        self.rain_density = rain_density;
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, Rounding};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_snow(elapsed)
+    ///     .with_head_rounding(Rounding::Nearest);
+    /// ```
+    ///
+    /// The whole drop moves with its head, so this shifts every glyph by up to half a row.
+    pub fn with_head_rounding(mut self, head_rounding: Rounding) -> Rain {
+        self.head_rounding = head_rounding;
         self
     }
 
-    /// Set the target speed for the rain.
+    /// Render one frame as styled text, e.g. to embed in a [`Paragraph`](ratatui::widgets::Paragraph).
     ///
-    /// Speed can be configured as an absolute value of pixels per second, or as a
-    /// preset.
+    /// Each row of the area becomes a [`Line`], with consecutive cells of the same style
+    /// merged into one [`Span`]. Cells without glyphs become unstyled spaces:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let text = Rain::new_matrix(Duration::from_secs(5)).to_text(area);
+    ///
+    /// assert_eq!(text.lines.len(), 10);
+    /// assert!(text.lines.iter().all(|line| line.width() == 20));
+    /// ```
+    ///
+    /// Only the area's size matters, since the text isn't positioned anywhere yet.
+    pub fn to_text(&self, area: Rect) -> Text<'static> {
+        let area = Rect::new(0, 0, area.width, area.height);
+        let mut buf = Buffer::empty(area);
+        Widget::render(self.clone(), area, &mut buf);
+
+        let lines: Vec<Line<'static>> = (0..area.height)
+            .map(|y| {
+                let mut spans: Vec<Span<'static>> = vec![];
+                for x in 0..area.width {
+                    let cell = &buf[(x, y)];
+                    match spans.last_mut() {
+                        Some(span) if span.style == cell.style() => {
+                            span.content.to_mut().push_str(cell.symbol())
+                        }
+                        _ => spans.push(Span::styled(cell.symbol().to_string(), cell.style())),
+                    }
+                }
+                Line::from(spans)
+            })
+            .collect();
+        Text::from(lines)
+    }
+
+    /// Set which side of each drop's head its tail is drawn on.
     ///
-    /// For an absolute speed in pixels per second:
+    /// By default, the tail trails behind the head like a streak. Drawing it ahead instead
+    /// makes the head lead from the back of the drop, for an odd anti-gravity look:
     ///
     /// ```
     /// use std::time::Duration;
-    /// use tui_rain::{Rain, RainSpeed};
+    /// use tui_rain::{Rain, TailDirection};
     ///
     /// let elapsed = Duration::from_secs(5);
     ///
     /// Rain::new_matrix(elapsed)
-    ///     .with_rain_speed(RainSpeed::Absolute {
-    ///         speed: 10.0,
-    ///     });
+    ///     .with_tail_direction(TailDirection::Ahead);
     /// ```
     ///
-    /// Preset options include:
+    /// A tail drawn ahead never wraps around to the other end of the screen, and is culled
+    /// once it passes the edge. Everything styled along the tail, like color stops and head
+    /// shadows, follows it to the other side.
+    pub fn with_tail_direction(mut self, tail_direction: TailDirection) -> Rain {
+        self.tail_direction = tail_direction;
+        self
+    }
+
+    /// Set the direction drops move in.
     ///
-    /// - `RainSpeed::Slow`
-    /// - `RainSpeed::Normal`
-    /// - `RainSpeed::Fast`
-    pub fn with_rain_speed(mut self, rain_speed: RainSpeed) -> Rain {
-        self.rain_speed = rain_speed;
+    /// Drops fall by default. Rising drops start at the bottom of the screen and move up,
+    /// with their tails trailing below, e.g. for embers or bubbles:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+    /// use tui_rain::{Rain, RainDirection, RainSpeed};
+    ///
+    /// let area = Rect::new(0, 0, 1, 20);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// // The head is 15 rows up from the bottom, with the tail reaching down to row 13.
+    /// Rain::new_matrix(Duration::from_secs_f64(5.5))
+    ///     .with_rain_speed(RainSpeed::Absolute { speed: 10.0 })
+    ///     .with_rain_speed_variance(0.0)
+    ///     .with_tail_lifespan(Duration::from_secs(1))
+    ///     .with_direction(RainDirection::Up)
+    ///     .render_single_drop(vec![0; 40], area, &mut buf);
+    ///
+    /// assert_eq!(buf[(0, 4)].fg, Color::White);
+    /// assert!((5..=13).all(|y| buf[(0, y)].symbol() != " "));
+    /// assert!([3, 14].iter().all(|&y| buf[(0, y)].symbol() == " "));
+    /// ```
+    ///
+    /// The direction flips everything about the drop, so the bold third of each drop is
+    /// still nearest its head, and the [tail direction](Rain::with_tail_direction) is
+    /// relative to the direction of motion.
+    pub fn with_direction(mut self, direction: RainDirection) -> Rain {
+        self.direction = direction;
         self
     }
 
-    /// Set the rain speed variance.
+    /// Compute the frame at the given elapsed duration once, to render into many buffers.
     ///
-    /// To avoid perfectly consistent patterns, you can configure some variance in the
-    /// speed of each drop. This can also give an impression of parallax (depth).
+    /// Building the glyphs is the expensive part of rendering, while writing them into a
+    /// buffer is cheap. Precomputing the frame separates the two, e.g. to share one frame
+    /// between several clients:
     ///
-    /// For example, a value of `0.1` will cause each drop's speed to be uniformly
-    /// distrbuted within ±10% of the target speed:
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let rain = Rain::new_matrix(Duration::ZERO);
+    /// let frame = rain.precompute(area, Duration::from_secs(5));
+    ///
+    /// let (mut first, mut second) = (Buffer::empty(area), Buffer::empty(area));
+    /// frame.render(&mut first);
+    /// frame.render(&mut second);
+    ///
+    /// let mut expected = Buffer::empty(area);
+    /// rain.with_elapsed(Duration::from_secs(5)).render(area, &mut expected);
+    /// assert_eq!(first, expected);
+    /// assert_eq!(second, expected);
+    /// ```
+    ///
+    /// The frame renders like [`Widget::render`] into the given area, so it doesn't include
+    /// anything from a [`RainState`].
+    pub fn precompute(&self, area: Rect, elapsed: Duration) -> PrecomputedFrame {
+        let rain = self.clone().with_elapsed(elapsed);
+        let glyphs = rain.build_glyphs(area);
+        PrecomputedFrame { rain, area, glyphs }
+    }
+
+    /// Set the color depth of the terminal, to quantize colors to what it can display.
+    ///
+    /// Effects like gradients, fades and blending compute arbitrary RGB colors, which
+    /// terminals with fewer colors may display unpredictably. With a lower color depth,
+    /// every color is mapped to the nearest color the terminal supports before styling:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+    /// use tui_rain::{ColorDepth, Rain};
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_color_stops(vec![(0.0, Color::White), (1.0, Color::Rgb(0, 64, 0))])
+    ///     .with_color_depth(ColorDepth::Ansi256)
+    ///     .render(area, &mut buf);
+    ///
+    /// assert!(buf.content().iter().any(|cell| matches!(cell.fg, Color::Indexed(_))));
+    /// assert!(buf.content().iter().all(|cell| !matches!(cell.fg, Color::Rgb(..))));
+    /// ```
+    ///
+    /// Defaults to [`ColorDepth::TrueColor`], which leaves colors unchanged.
+    pub fn with_color_depth(mut self, color_depth: ColorDepth) -> Rain {
+        self.color_depth = color_depth;
+        self
+    }
+
+    /// Set whether to invert the brightness gradient along each drop.
+    ///
+    /// Inverted drops are brightest at the end of the tail and dimmest just behind the
+    /// head. This flips the [bold / dim effect](Rain::with_bold_dim_effect), as well as
+    /// [color stops](Rain::with_color_stops) and [indexed gradients](Rain::with_indexed_gradient):
     ///
     /// ```
     /// use std::time::Duration;
@@ -322,20 +4401,79 @@ impl Rain {
     /// let elapsed = Duration::from_secs(5);
     ///
     /// Rain::new_matrix(elapsed)
-    ///     .with_rain_speed_variance(0.1);
+    ///     .with_invert_gradient(true);
     /// ```
     ///
-    /// The speed of an individual drop will never go below 0.001 pixels / second, but
-    /// can vary arbitrarily high.
-    pub fn with_rain_speed_variance(mut self, rain_speed_variance: f64) -> Rain {
-        self.rain_speed_variance = rain_speed_variance;
+    /// Without a gradient, the head keeps its own color. Defaults to `false`.
+    pub fn with_invert_gradient(mut self, invert_gradient: bool) -> Rain {
+        self.invert_gradient = invert_gradient;
         self
     }
 
-    /// Set the tail lifespan for the rain.
+    /// Set a time budget for building each frame, to degrade gracefully under load.
     ///
-    /// You can make the rain drop tails appear shorter / longer by configuring how long
-    /// the tail effect lasts:
+    /// Once building a frame has taken longer than the budget, the remaining drops are
+    /// built with only their heads. Heads are never skipped, so the rain stays
+    /// recognizable while the oldest glyphs of the tails are the first to go:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// Rain::new_matrix(Duration::from_secs(5))
+    ///     .with_time_budget(Duration::from_millis(2))
+    ///     .render(area, &mut buf);
+    /// ```
+    ///
+    /// When the budget is exceeded, frames depend on how fast they were built, so they're
+    /// no longer reproducible. By default, there's no budget.
+    pub fn with_time_budget(mut self, time_budget: Duration) -> Rain {
+        self.time_budget = Some(time_budget);
+        self
+    }
+
+    /// Add a fog of faint, unmoving glyphs behind the drops.
+    ///
+    /// The density is the fraction of cells that contain a fog glyph. Each of them shimmers
+    /// with the same noise as the drops, and is always drawn underneath them. This gives
+    /// the rain a sense of depth, with many glyphs on screen but only a few drop heads:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::{Rain, RainDensity};
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_rain_density(RainDensity::Sparse)
+    ///     .with_fog(0.3);
+    /// ```
+    ///
+    /// Fog glyphs are dim, and half as bright as the drops' color. Defaults to `0.0`, with
+    /// no fog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the density is not within [0, 1].
+    pub fn with_fog(mut self, density: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&density),
+            "fog density must be within [0, 1]"
+        );
+        self.fog = density;
+        self
+    }
+
+    /// Set whether each drop's track length varies from cycle to cycle.
+    ///
+    /// Every drop has a fixed track length, so over a long time its pattern of appearing
+    /// and disappearing becomes periodic. With varying tracks, each cycle's track is
+    /// shortened by a random amount within the [track length range](Rain::with_track_length_range),
+    /// chosen from the drop's entropy for that cycle:
     ///
     /// ```
     /// use std::time::Duration;
@@ -344,19 +4482,22 @@ impl Rain {
     /// let elapsed = Duration::from_secs(5);
     ///
     /// Rain::new_matrix(elapsed)
-    ///     .with_tail_lifespan(Duration::from_secs(5));
+    ///     .with_varying_tracks(true);
     /// ```
     ///
-    /// The drop length is capped at the screen height to avoid strange wraparound
-    /// effects.
-    pub fn with_tail_lifespan(mut self, tail_lifespan: Duration) -> Rain {
-        self.tail_lifespan = tail_lifespan;
+    /// Drops keep their speed, and how often they start a cycle. A shorter track instead
+    /// delays when the drop enters the top of the screen within its cycle, so each drop's
+    /// phase shifts slightly from cycle to cycle. Tracks are never shortened below the
+    /// height of the screen. Defaults to `false`.
+    pub fn with_varying_tracks(mut self, varying_tracks: bool) -> Rain {
+        self.varying_tracks = varying_tracks;
         self
     }
 
-    /// Set the color for the rain.
+    /// Occasionally flip glyphs to reverse video, for a flickering glitch effect.
     ///
-    /// You can change the tail color for each drop:
+    /// The rate is the chance that each glyph is glitched at any moment. Glitches last for
+    /// 50ms, a frame or two at typical framerates, and are reproducible from the seed:
     ///
     /// ```
     /// use std::time::Duration;
@@ -365,20 +4506,28 @@ impl Rain {
     /// let elapsed = Duration::from_secs(5);
     ///
     /// Rain::new_matrix(elapsed)
-    ///     .with_color(ratatui::style::Color::LightGreen);
+    ///     .with_glitch(0.01);
     /// ```
     ///
-    /// The color of the head is [independently configured](Rain::with_head_color). The
-    /// bold / dim effects that automatically get applied over a drop's length may tweak
-    /// the color inadvertently, but [this can be disabled](Rain::with_bold_dim_effect).
-    pub fn with_color(mut self, color: Color) -> Rain {
-        self.color = color;
+    /// Defaults to `0.0`, with no glitches.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate is not within [0, 1].
+    pub fn with_glitch(mut self, rate: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&rate),
+            "glitch rate must be within [0, 1]"
+        );
+        self.glitch = rate;
         self
     }
 
-    /// Set the head color for the rain.
+    /// Reflect the rain in a puddle at the bottom of the area.
     ///
-    /// You can change the head color for each drop:
+    /// The rain falls in the area above the given number of rows at the bottom, which show
+    /// a vertically flipped copy of the rain just above them. The reflection is dimmed
+    /// toward black by the given amount, from `0.0` (as bright as the rain) to `1.0`:
     ///
     /// ```
     /// use std::time::Duration;
@@ -387,115 +4536,1153 @@ impl Rain {
     /// let elapsed = Duration::from_secs(5);
     ///
     /// Rain::new_matrix(elapsed)
-    ///     .with_head_color(ratatui::style::Color::Green);
+    ///     .with_reflection(4, 0.6);
     /// ```
     ///
-    /// The color of the tail is [independently configured](Rain::with_color). The
-    /// bold / dim effects that automatically get applied over a drop's length may tweak
-    /// the color inadvertently, but [this can be disabled](Rain::with_bold_dim_effect).
-    pub fn with_head_color(mut self, head_color: Color) -> Rain {
-        self.head_color = head_color;
+    /// The reflection is at most half the height of the area. By default, there's no
+    /// reflection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimming is not within [0, 1].
+    pub fn with_reflection(mut self, height: u16, dim: f64) -> Rain {
+        assert!(
+            (0.0..=1.0).contains(&dim),
+            "reflection dimming must be within [0, 1]"
+        );
+        self.reflection = Some((height, dim));
         self
     }
 
-    /// Set whether to apply the bold / dim effect.
+    /// Set how many cycles each drop lives for, before going dark and being reborn.
+    ///
+    /// Drops normally cycle down the screen forever. With a lifespan, each drop goes dark
+    /// after the given number of cycles, for a gap of between 1 cycle and the lifespan
+    /// that's stable for the drop. It's then reborn in a fresh column:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_rain::Rain;
+    ///
+    /// let elapsed = Duration::from_secs(5);
+    ///
+    /// Rain::new_matrix(elapsed)
+    ///     .with_drop_lifespan(3);
+    /// ```
+    ///
+    /// Drops usually change column every cycle anyway. When they
+    /// [drift](Rain::with_column_drift) instead, the drift restarts from a fresh column each
+    /// life. Drops fixed to a column, e.g. without overlap, stay in it. By default, drops
+    /// live forever.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lifespan is 0 cycles.
+    pub fn with_drop_lifespan(mut self, cycles: u32) -> Rain {
+        assert!(cycles > 0, "drop lifespan must be at least 1 cycle");
+        self.drop_lifespan = Some(cycles);
+        self
+    }
+
+    /// Compute which cells would contain a glyph if rendered to the given area.
+    ///
+    /// This is useful for integrations that need to know where the rain is, such as
+    /// detecting collisions with sprites layered over it:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let occupancy = Rain::new_matrix(Duration::from_secs(5)).occupancy(area);
+    ///
+    /// let (x, y) = (3, 4);
+    /// let occupied = occupancy[y * area.width as usize + x];
+    /// ```
+    ///
+    /// The result is in row-major order, with `area.width * area.height` entries.
+    pub fn occupancy(&self, area: Rect) -> Vec<bool> {
+        let mut occupancy = vec![false; area.area() as usize];
+        for glyph in self.build_glyphs(area) {
+            occupancy[glyph.y as usize * area.width as usize + glyph.x as usize] = true;
+        }
+        occupancy
+    }
+
+    /// Compute the color of the glyph that would be drawn on top of a cell, if any.
+    ///
+    /// This runs the same layout as rendering, without needing a buffer, so the rain can
+    /// be sampled to tint the surrounding UI:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let rain = Rain::new_matrix(Duration::from_secs(5));
+    ///
+    /// // The colors match the rendered rain, and are empty where nothing is drawn.
+    /// let mut buf = Buffer::empty(area);
+    /// rain.clone().render(area, &mut buf);
+    /// for (i, cell) in buf.content().iter().enumerate() {
+    ///     let (x, y) = buf.pos_of(i);
+    ///     let color = rain.color_at(x, y, area);
+    ///     assert_eq!(color, (cell.symbol() != " ").then_some(cell.fg));
+    /// }
+    /// assert!(buf.content().iter().any(|cell| cell.symbol() != " "));
+    /// ```
+    ///
+    /// The position is relative to the origin of the area, and cells outside it are
+    /// empty. Blending isn't taken into account, since it depends on the buffer.
+    pub fn color_at(&self, x: u16, y: u16, area: Rect) -> Option<Color> {
+        if x >= area.width || y >= area.height {
+            return None;
+        }
+        self.build_glyphs(area)
+            .into_iter()
+            .rev()
+            .find(|glyph| {
+                glyph.x == x && glyph.y == y && !self.transparent_chars.contains(&glyph.content)
+            })
+            .and_then(|glyph| glyph.style.fg)
+    }
+
+    /// Estimate statistics about the rain in the given area, without rendering it.
+    ///
+    /// This is useful for tuning density and speed interactively, such as showing the
+    /// expected number of drops in a configuration UI:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let stats = Rain::new_matrix(Duration::from_secs(5)).preview(Rect::new(0, 0, 80, 24));
+    ///
+    /// assert_eq!(stats.num_drops, 38);
+    /// ```
+    ///
+    /// The cycle time and glyph count are approximate, since they are computed from
+    /// the target speed rather than each drop's actual speed.
+    pub fn preview(&self, area: Rect) -> RainStats {
+        let area = self.pixel_grid(area);
+        let num_drops = self.num_drops(area);
+        let rain_speed = self.base_speed().max(1e-3);
+
+        // Tracks are uniformly distributed within the track length range.
+        let (min_track_len, track_len_spread) = self.track_len_bounds(area.height);
+        let mean_track_len = min_track_len as f64 + track_len_spread as f64 / 2.0;
+
+        // Each of the 2n drops is visible for about `height / track_len` of its cycle, so
+        // on average n drops worth of glyphs are on screen.
+        let drop_len = self.mean_drop_len(area.height);
+
+        RainStats {
+            num_drops,
+            average_cycle_time: Duration::from_secs_f64(mean_track_len / rain_speed),
+            expected_glyphs: (num_drops as f64 * drop_len.floor()) as usize
+                * (self.pixel_scale as usize).pow(2),
+        }
+    }
+
+    /// Render the rain like [`Widget::render`], and report statistics about the frame.
+    ///
+    /// This is useful for understanding the performance cost of a configuration:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut buf = Buffer::empty(area);
+    ///
+    /// let stats = Rain::new_matrix(Duration::from_secs(5)).render_with_stats(area, &mut buf);
+    ///
+    /// let drawn = buf.content().iter().filter(|cell| cell.symbol() != " ").count();
+    /// assert!(stats.glyphs > 0);
+    /// assert!(stats.glyphs >= drawn);
+    /// ```
+    pub fn render_with_stats(self, area: Rect, buf: &mut Buffer) -> RenderStats {
+        let mut stats = RenderStats::default();
+        let glyphs = self.build_glyphs_with_stats(area, EntryBounds::default(), &mut stats);
+        self.warn_if_slow(glyphs.len());
+        self.clear_area(area, buf);
+        self.write_glyphs(glyphs, area, buf);
+        stats
+    }
+
+    /// Render successive frames at a fixed framerate, starting from the elapsed duration.
+    ///
+    /// Each frame is an independently rendered buffer, advancing the elapsed duration by
+    /// `1 / fps`. This is useful for recording or exporting an animation offline:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let rain = Rain::new_matrix(Duration::from_secs(5));
+    ///
+    /// let frames: Vec<_> = rain.frames(area, 30.0).take(90).collect();
+    ///
+    /// assert_eq!(frames.len(), 90);
+    /// ```
+    ///
+    /// The iterator never ends, so limit it with something like [`Iterator::take`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the framerate is not positive and finite.
+    pub fn frames(&self, area: Rect, fps: f64) -> impl Iterator<Item = Buffer> {
+        assert!(
+            fps.is_finite() && fps > 0.0,
+            "framerate must be positive and finite"
+        );
+        let rain = self.clone();
+        (0u64..).map(move |frame| {
+            let mut buf = Buffer::empty(area);
+            let elapsed = rain.elapsed + Duration::from_secs_f64(frame as f64 / fps);
+            Widget::render(rain.clone().with_elapsed(elapsed), area, &mut buf);
+            buf
+        })
+    }
+
+    /// Render a single drop built from the given entropy, for debugging.
+    ///
+    /// Every drop is built from a vector of random values, which fully determines its
+    /// speed, phase, columns, and noise. Rendering one drop in isolation with a known
+    /// entropy vector makes it easier to reason about the effect of each setting:
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::Rain;
     ///
-    /// By default, the lower third of each drop has the bold effect applied, and the
-    /// upper third has the dim effect applied. This produces an impression of the drop
-    /// fading instead of abruptly ending.
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
     ///
-    /// This may tweak the color of glyphs away from the base color on some terminals,
-    /// so it can be disabled if desired:
+    /// let entropy = (0..20).map(|i| i * (u64::MAX / 20)).collect();
+    /// Rain::new_matrix(Duration::from_secs(5)).render_single_drop(entropy, area, &mut buf);
+    /// ```
+    ///
+    /// The entropy vector's length becomes the drop's track length, so it must be at
+    /// least as long as the area's height, and longer for the drop to spend time off the
+    /// screen.
+    ///
+    /// Rendering frame by frame also shows how a drop wraps around. With a track exactly as
+    /// tall as the area and a fixed column, the drop's head reappears at the top on the same
+    /// frame it leaves the bottom, so the column is never empty once the drop has appeared:
     ///
     /// ```
     /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
     /// use tui_rain::Rain;
     ///
-    /// let elapsed = Duration::from_secs(5);
+    /// let area = Rect::new(0, 0, 1, 10);
+    /// let entropy: Vec<u64> = (0..10).map(|i| i * (u64::MAX / 10)).collect();
     ///
-    /// Rain::new_matrix(elapsed)
-    ///     .with_bold_dim_effect(false);
-    ///```
-    pub fn with_bold_dim_effect(mut self, bold_dim_effect: bool) -> Rain {
-        self.bold_dim_effect = bold_dim_effect;
-        self
+    /// let mut appeared = false;
+    /// for frame in 0..1200 {
+    ///     let mut buf = Buffer::empty(area);
+    ///     Rain::new_matrix(Duration::from_secs_f64(frame as f64 / 60.0))
+    ///         .with_column_drift(0)
+    ///         .render_single_drop(entropy.clone(), area, &mut buf);
+    ///
+    ///     let empty = buf.content().iter().all(|cell| cell.symbol() == " ");
+    ///     assert!(!(appeared && empty), "the drop disappeared on frame {frame}");
+    ///     appeared |= !empty;
+    /// }
+    /// assert!(appeared);
+    /// ```
+    ///
+    /// A track shorter than the area can't be rendered:
+    ///
+    /// ```should_panic
+    /// use std::time::Duration;
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::Rain;
+    ///
+    /// let area = Rect::new(0, 0, 10, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// Rain::new_matrix(Duration::from_secs(5)).render_single_drop(vec![1, 2, 3], area, &mut buf);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entropy vector is shorter than the area's height, divided by the
+    /// scale when using [`Rain::with_pixel_scale`].
+    pub fn render_single_drop(&self, entropy: Vec<u64>, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let grid = self.pixel_grid(area);
+        let half = self.mirror_half(grid);
+        assert!(
+            entropy.len() >= half.height as usize,
+            "entropy has {} values, but the track must be at least {} tall to fit the area",
+            entropy.len(),
+            half.height
+        );
+        let mut glyphs = self.build_drop(
+            entropy,
+            None,
+            half.width,
+            half.height,
+            EntryBounds::default(),
+            &mut RenderStats::default(),
+        );
+        glyphs.sort_by(|a, b| self.glyph_priority.compare(a, b));
+        let glyphs = self.mirror_glyphs(glyphs, grid);
+        self.write_glyphs(self.scale_glyphs(glyphs, area), area, buf);
     }
 
-    /// Set the interval between random character changes.
+    /// Render the rain like [`StatefulWidget::render`], encoded as the ANSI escape
+    /// sequences that update a terminal from the last frame rendered with the state.
     ///
-    /// A more subtle effect is that glyphs already rendered in a drop occasionally
-    /// switch characters before dissapearing. The time interval between each character
-    /// switch is per-glyph, and can be adjusted:
+    /// This suits streaming the rain to remote terminals, e.g. from a telnet or SSH
+    /// server, since only the cells that changed are sent:
     ///
     /// ```
-    /// use std::time::Duration;
-    /// use tui_rain::Rain;
+    /// use std::{io::Write, time::Duration};
+    /// use ratatui::layout::Rect;
+    /// use tui_rain::{Rain, RainState};
     ///
-    /// let elapsed = Duration::from_secs(5);
+    /// let area = Rect::new(0, 0, 80, 24);
+    /// let mut state = RainState::default();
+    /// let mut connection = vec![]; // e.g. a TcpStream
     ///
-    /// Rain::new_matrix(elapsed)
-    ///     .with_noise_interval(Duration::from_secs(10));
+    /// // Clear the screen, then send each frame.
+    /// connection.write_all(b"\x1b[2J").unwrap();
+    /// for frame in 0..60 {
+    ///     let elapsed = Duration::from_secs_f64(frame as f64 / 30.0);
+    ///     let bytes = Rain::new_matrix(elapsed).render_ansi_diff(area, &mut state);
+    ///     connection.write_all(&bytes).unwrap();
+    /// }
     /// ```
-    pub fn with_noise_interval(mut self, noise_interval: Duration) -> Rain {
-        self.noise_interval = noise_interval;
-        self
+    ///
+    /// The first frame, and any frame after the area changes, is encoded as if the last
+    /// frame was blank. Cursor positions are relative to the area's origin. See
+    /// [`encode_ansi_diff`] for the escape sequences used.
+    pub fn render_ansi_diff(self, area: Rect, state: &mut RainState) -> Vec<u8> {
+        let mut buf = Buffer::empty(area);
+        StatefulWidget::render(self, area, &mut buf, state);
+
+        let previous = match state.last_buffer.take() {
+            Some(previous) if previous.area == area => previous,
+            _ => Buffer::empty(area),
+        };
+        let bytes = encode_ansi_diff(&previous, &buf);
+        state.last_buffer = Some(buf);
+        bytes
     }
 
-    /// Set the character set for the drops.
+    /// Render the rain like [`StatefulWidget::render`], calling back with the column of
+    /// each drop that reaches the bottom row of the area.
     ///
-    /// The simplest option is to provide an explicit set of characters to choose from:
+    /// This turns the rain into something interactive, e.g. for a game of catching drops:
     ///
     /// ```
     /// use std::time::Duration;
-    /// use tui_rain::{CharacterSet, Rain};
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::{Rain, RainState};
     ///
-    /// let elapsed = Duration::from_secs(5);
+    /// let area = Rect::new(0, 0, 20, 10);
+    /// let mut buf = Buffer::empty(area);
+    /// let mut state = RainState::default();
     ///
-    /// Rain::new_matrix(elapsed)
-    ///     .with_character_set(CharacterSet::Explicit {
-    ///         options: vec!['a', 'b', 'c'],
+    /// let mut caught = vec![];
+    /// for frame in 0..600 {
+    ///     let elapsed = Duration::from_secs_f64(frame as f64 / 60.0);
+    ///     Rain::new_rain(elapsed).render_with_on_bottom(area, &mut buf, &mut state, |x| {
+    ///         caught.push(x)
     ///     });
+    /// }
+    ///
+    /// assert!(!caught.is_empty());
+    /// assert!(caught.iter().all(|x| *x < area.width));
     /// ```
     ///
-    /// More performant is to provide a unicode range:
+    /// Each drop is reported once per fall, on the first frame it's in the bottom row,
+    /// even if it moves several rows per frame. [Rising](Rain::with_direction) drops are
+    /// reported when they reach the top row instead. The columns are relative to the
+    /// area's origin. Nothing is reported on the first frame rendered with a state, or
+    /// while paused.
+    ///
+    /// Nothing is reported for an empty area, e.g. in a collapsed layout:
     ///
     /// ```
     /// use std::time::Duration;
-    /// use tui_rain::{CharacterSet, Rain};
+    /// use ratatui::{buffer::Buffer, layout::Rect};
+    /// use tui_rain::{Rain, RainState};
     ///
-    /// let elapsed = Duration::from_secs(5);
+    /// let area = Rect::new(0, 0, 10, 0);
+    /// let mut buf = Buffer::empty(area);
+    /// let mut state = RainState::default();
     ///
-    /// Rain::new_matrix(elapsed)
-    ///     .with_character_set(CharacterSet::UnicodeRange {
-    ///         start: 0x61,
-    ///         len: 26,
+    /// for frame in 0..5 {
+    ///     let elapsed = Duration::from_secs(frame);
+    ///     Rain::new_rain(elapsed).render_with_on_bottom(area, &mut buf, &mut state, |_| {
+    ///         panic!("nothing can reach the bottom of an empty area")
     ///     });
+    /// }
     /// ```
+    pub fn render_with_on_bottom(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut RainState,
+        mut on_bottom: impl FnMut(u16),
+    ) {
+        let (rain, entry) = self.with_state(state);
+
+        // Report each drop in the bottom row now that wasn't there on the last frame.
+        // An empty area has no bottom row, so nothing can reach it.
+        if let Some(last_elapsed) = entry.last_elapsed.filter(|_| !area.is_empty()) {
+            let previous = rain.clone().with_elapsed(last_elapsed);
+            let grid = rain.pixel_grid(area);
+            let half = rain.mirror_half(grid);
+            let mut stats = RenderStats::default();
+            let end_row = match rain.direction {
+                RainDirection::Down => grid.height - 1,
+                RainDirection::Up => 0,
+            };
+            let mut bottom_columns = |rain: &Rain, entropy, column| -> Vec<u16> {
+                let glyphs =
+                    rain.build_drop(entropy, column, half.width, half.height, entry, &mut stats);
+                rain.mirror_glyphs(glyphs, grid)
+                    .into_iter()
+                    .filter(|glyph| glyph.y == end_row)
+                    .map(|glyph| glyph.x)
+                    .collect()
+            };
+            for (entropy, column) in rain.build_drops(half) {
+                let before = bottom_columns(&previous, entropy.clone(), column);
+                for x in bottom_columns(&rain, entropy, column) {
+                    if !before.contains(&x) {
+                        on_bottom(x * rain.pixel_scale);
+                    }
+                }
+            }
+        }
+
+        rain.render_with_ghosts(area, buf, state, entry)
+    }
+
+    /// Render with the state already applied, remembering and drawing any ghost trail.
+    fn render_with_ghosts(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        state: &mut RainState,
+        entry: EntryBounds,
+    ) {
+        let glyphs = self.build_glyphs_cached(area, state, entry);
+        self.warn_if_slow(glyphs.len());
+        self.clear_area(area, buf);
+
+        if let Some((decay, color)) = self.ghost_trail {
+            // Forget the ghosts if the area changed, since they no longer line up.
+            if state.ghost_area != area {
+                state.ghost_area = area;
+                state.ghost_cells = vec![None; area.area() as usize];
+            }
+
+            // The topmost glyph in each cell is remembered, since it's drawn last.
+            for glyph in &glyphs {
+                let index = glyph.y as usize * area.width as usize + glyph.x as usize;
+                state.ghost_cells[index] = Some((self.elapsed, glyph.content));
+            }
+
+            // Draw ghosts underneath the rain, fading out as they age.
+            for (index, cell) in state.ghost_cells.iter().enumerate() {
+                let Some((seen, content)) = *cell else {
+                    continue;
+                };
+                let age = self.clock_secs(self.elapsed) - self.clock_secs(seen);
+                if age >= decay.as_secs_f64() {
+                    continue;
+                }
+                let fade = age / decay.as_secs_f64();
+                let position = (
+                    area.x + (index % area.width as usize) as u16,
+                    area.y + (index / area.width as usize) as u16,
+                );
+                buf[position].set_char(content);
+                let color = self
+                    .color_depth
+                    .quantize(lerp_color(color, Color::Black, fade));
+                buf[position].set_style(Style::default().fg(color));
+            }
+        }
+
+        self.write_glyphs(glyphs, area, buf);
+    }
+
+    /// Hash the configuration apart from the time, to tell if it's changed between frames.
+    fn config_hash(&self) -> u64 {
+        let mut config = self.clone();
+        config.elapsed = Duration::ZERO;
+        let mut hasher = DefaultHasher::new();
+        config.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Warn once per configuration when a frame has enough glyphs to be slow to render.
     ///
-    /// Preset unicode ranges include:
+    /// Only the most recent slow configurations are remembered, so an app that keeps
+    /// changing its configuration doesn't grow the memory of warnings without bound.
+    #[cfg(feature = "log")]
+    fn warn_if_slow(&self, glyphs: usize) {
+        static WARNED: Mutex<VecDeque<u64>> = Mutex::new(VecDeque::new());
+        if glyphs <= SLOW_GLYPH_COUNT {
+            return;
+        }
+        let config = self.config_hash();
+        let mut warned = WARNED.lock().unwrap_or_else(PoisonError::into_inner);
+        if !warned.contains(&config) {
+            if warned.len() == WARNED_CONFIGS {
+                warned.pop_front();
+            }
+            warned.push_back(config);
+            log::warn!(
+                "tui-rain rendered {glyphs} glyphs in one frame, which may be slow. \
+                 Consider capping the number of drops with `Rain::with_max_drops`."
+            );
+        }
+    }
+
+    /// Warn once per configuration when a frame has enough glyphs to be slow to render.
+    #[cfg(not(feature = "log"))]
+    fn warn_if_slow(&self, _glyphs: usize) {}
+
+    /// Build every glyph to render, reusing the last computed frame if it's recent enough.
+    fn build_glyphs_cached(
+        &self,
+        area: Rect,
+        state: &mut RainState,
+        entry: EntryBounds,
+    ) -> Vec<Glyph> {
+        let Some(min_frame_interval) = self.min_frame_interval else {
+            return self.build_glyphs_with_stats(area, entry, &mut RenderStats::default());
+        };
+
+        let config = self.config_hash();
+
+        if let Some(frame) = &state.last_frame {
+            let since = self.elapsed.checked_sub(frame.elapsed);
+            if frame.config == config
+                && frame.area == area
+                && since.is_some_and(|since| since < min_frame_interval)
+            {
+                // Ages aren't kept, since they're only needed to sort the glyphs.
+                return frame
+                    .glyphs
+                    .iter()
+                    .map(|&(x, y, content, style)| Glyph {
+                        x,
+                        y,
+                        age: 0.0,
+                        content,
+                        style,
+                    })
+                    .collect();
+            }
+        }
+
+        let glyphs = self.build_glyphs_with_stats(area, entry, &mut RenderStats::default());
+        state.last_frame = Some(Frame {
+            config,
+            area,
+            elapsed: self.elapsed,
+            glyphs: glyphs
+                .iter()
+                .map(|glyph| (glyph.x, glyph.y, glyph.content, glyph.style))
+                .collect(),
+        });
+        glyphs
+    }
+
+    /// Apply and advance a state, to render as a [`StatefulWidget`].
+    fn with_state(self, state: &mut RainState) -> (Rain, EntryBounds) {
+        let elapsed = state.effective_elapsed(self.elapsed);
+
+        // Track when rendering began and the previous frame, for top entry only.
+        let entry = EntryBounds {
+            first_elapsed: *state.first_elapsed.get_or_insert(elapsed),
+            last_elapsed: state.last_elapsed.replace(elapsed),
+        };
+
+        (self.with_elapsed(elapsed), entry)
+    }
+
+    /// Compute the minimum track length and the spread of track lengths above it.
     ///
-    /// - `CharacterSet::HalfKana` is the half-width Japanese kana character set (used
-    ///   in the classic matrix rain)
-    /// - `CharacterSet::Lowercase` is the lowercase English character set
-    pub fn with_character_set(mut self, character_set: CharacterSet) -> Rain {
-        self.character_set = character_set;
-        self
+    /// The spread is at least 1 so it can be used as a modulus.
+    fn track_len_bounds(&self, height: u16) -> (u64, u64) {
+        let (min_mult, max_mult) = self.track_length_range;
+        let min_track_len = (height as f64 * min_mult) as u64;
+        let track_len_spread = ((max_mult - min_mult) * height as f64).round() as u64;
+        (min_track_len, track_len_spread.max(1))
+    }
+
+    /// Build the rng. Uses the configured portable and reproducible rng.
+    fn build_rng(&self) -> impl RngCore {
+        RainRng::new(self.rng_kind, self.seed ^ mix(self.instance_offset))
+    }
+
+    /// Build every glyph to render in the given area, sorted by priority in render order.
+    fn build_glyphs(&self, area: Rect) -> Vec<Glyph> {
+        self.build_glyphs_with_stats(area, EntryBounds::default(), &mut RenderStats::default())
+    }
+
+    /// Write glyphs to the buffer in order, relative to the area's origin.
+    fn write_glyphs(&self, glyphs: Vec<Glyph>, area: Rect, buf: &mut Buffer) {
+        let glyphs = if self.scanline_output {
+            self.scanline_order(glyphs, area)
+        } else {
+            glyphs
+        };
+
+        for glyph in glyphs {
+            if self.transparent_chars.contains(&glyph.content) {
+                continue;
+            }
+
+            let position = (area.x + glyph.x, area.y + glyph.y);
+
+            // Let a cell merge function decide the whole cell, if there is one.
+            if let Some(merge) = &self.cell_merge {
+                buf[position] = (merge.0)(&buf[position], &glyph);
+                continue;
+            }
+
+            let mut style = glyph.style;
+
+            // Blend the glyph's color with the color already in the cell.
+            if self.blend < 1.0 {
+                if let Some(fg) = style.fg {
+                    let color = lerp_color(buf[position].fg, fg, self.blend);
+                    style.fg = Some(self.color_depth.quantize(color));
+                }
+            }
+
+            buf[position].set_char(glyph.content);
+            buf[position].set_style(style);
+        }
+    }
+
+    /// Reset every cell in the area to a blank space, if clearing is enabled.
+    fn clear_area(&self, area: Rect, buf: &mut Buffer) {
+        if !self.clear {
+            return;
+        }
+        let area = area.intersection(buf.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf[(x, y)].reset();
+            }
+        }
+    }
+
+    /// Keep only the glyph drawn on top of each cell, in scanline order.
+    fn scanline_order(&self, glyphs: Vec<Glyph>, area: Rect) -> Vec<Glyph> {
+        // Glyphs are in render order, so later glyphs win. Transparent glyphs never win.
+        let mut cells: Vec<Option<Glyph>> = vec![None; area.area() as usize];
+        for glyph in glyphs {
+            if !self.transparent_chars.contains(&glyph.content) {
+                cells[glyph.y as usize * area.width as usize + glyph.x as usize] = Some(glyph);
+            }
+        }
+        cells.into_iter().flatten().collect()
+    }
+
+    /// Get the character set for glyphs in the given row.
+    fn character_set_for_row(&self, y: u16) -> Cow<'_, CharacterSet> {
+        match &self.character_set_by_row {
+            Some(by_row) => Cow::Owned((by_row.0)(y)),
+            None => Cow::Borrowed(&self.character_set),
+        }
+    }
+
+    /// Get the speed drops fall at before variance, in rows per second.
+    fn base_speed(&self) -> f64 {
+        match self.velocity {
+            Some((_, vy)) => vy,
+            None => self.rain_speed.speed(self.cell_aspect),
+        }
+    }
+
+    /// Get the character for a seed from a character set, in shuffled order if enabled.
+    fn character(&self, character_set: &CharacterSet, seed: u32) -> char {
+        if !self.shuffled_charset {
+            return character_set.get(seed);
+        }
+        let size = (character_set.size() as u32).max(1);
+        character_set.get(permute_index(
+            seed % size,
+            size,
+            mix(self.seed ^ SHUFFLE_SALT),
+        ))
+    }
+
+    /// Estimate the mean length of a drop in rows, from the speed and tail lifespan.
+    fn mean_drop_len(&self, height: u16) -> f64 {
+        let drop_len = if self.tail_length_weights.is_empty() {
+            self.base_speed().max(1e-3) * self.tail_lifespan.as_secs_f64()
+        } else {
+            let weights = &self.tail_length_weights;
+            let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+            let sum: f64 = weights
+                .iter()
+                .map(|(tail_length, weight)| *tail_length as f64 * weight)
+                .sum();
+            sum / total
+        };
+        drop_len.max(1.0).min(height as f64)
+    }
+
+    /// Compute the number of drops for the area, limited by the minimum and maximum if set.
+    ///
+    /// In a storm cycle, this is enough drops for the denser of the calm and the storm.
+    fn num_drops(&self, area: Rect) -> usize {
+        let drop_len = self.mean_drop_len(area.height);
+        let num_drops = match self.storm_cycle {
+            Some((_, calm_density, storm_density)) => calm_density
+                .num_drops(area, drop_len)
+                .max(storm_density.num_drops(area, drop_len)),
+            None => self.rain_density.num_drops(area, drop_len),
+        };
+
+        // The minimum is at most one drop per column, so tiny areas aren't overwhelmed.
+        let num_drops = num_drops.max(self.min_drops.min(area.width as usize));
+        match self.max_drops {
+            Some(max_drops) => num_drops.min(max_drops),
+            None => num_drops,
+        }
+    }
+
+    /// Build the entropy vec of every drop in the given area, and any column it's fixed to.
+    fn build_drops(&self, area: Rect) -> Vec<(Vec<u64>, Option<u16>)> {
+        // We don't actually have n drops with tracks equal to the screen height.
+        // We actually have 2n drops with tracks ranging from (by default) 1.5 to 2.5 the
+        // screen height. This introduces more randomness to the apparent n and reduces
+        // cyclic appearance.
+        let mut num_drops = self.num_drops(area) * 2;
+
+        // Without overlap, there can be at most one drop per column.
+        if self.no_overlap {
+            num_drops = num_drops.min(area.width as usize);
+        }
+
+        // Nothing can be rendered without drops or without space, so skip allocating.
+        if num_drops == 0 || area.is_empty() {
+            return vec![];
+        }
+
+        let mut rng = self.build_rng();
+
+        let (min_track_len, track_len_spread) = self.track_len_bounds(area.height);
+        let drop_track_lens: Vec<usize> = (0..num_drops)
+            .map(|_| (min_track_len + rng.next_u64() % track_len_spread) as usize)
+            .collect();
+
+        // We construct entropy consistently every frame to mimic statefulness.
+        // This is not a performance bottleneck, so caching wouldn't deliver much benefit.
+        let entropy: Vec<Vec<u64>> = drop_track_lens
+            .iter()
+            .map(|track_len| (0..*track_len).map(|_| rng.next_u64()).collect())
+            .collect();
+
+        // Without overlap, assign each drop a unique column from a shuffle of all columns.
+        let columns: Vec<Option<u16>> = if self.no_overlap {
+            let mut columns: Vec<u16> = (0..area.width).collect();
+            for i in (1..columns.len()).rev() {
+                columns.swap(i, (rng.next_u64() % (i as u64 + 1)) as usize);
+            }
+            columns.into_iter().map(Some).take(num_drops).collect()
+        } else {
+            vec![None; num_drops]
+        };
+
+        entropy.into_iter().zip(columns).collect()
+    }
+
+    /// Build every glyph to render in the given area, and record statistics about it.
+    fn build_glyphs_with_stats(
+        &self,
+        area: Rect,
+        entry: EntryBounds,
+        stats: &mut RenderStats,
+    ) -> Vec<Glyph> {
+        // Nothing can be rendered before the active window starts.
+        if let Some((start, _)) = self.active_window {
+            if self.clock_secs(self.elapsed) < start.as_secs_f64() {
+                return vec![];
+            }
+        }
+
+        // With a reflection, the rain only falls in the area above it.
+        let full_area = area;
+        let area = self.reflection_split(area).0;
+
+        // Rain is computed on a grid of pixels, which are scaled up to cells afterwards.
+        // When mirroring, it's only computed on half of the grid and reflected after.
+        let grid = self.pixel_grid(area);
+        let half = self.mirror_half(grid);
+
+        // Once over the time budget, the remaining drops are built with only their heads.
+        let build_start = Instant::now();
+        let heads_only = self
+            .time_budget
+            .map(|budget| (budget, self.clone().with_heads_only(true)));
+
+        // For every entropy vec, construct a single drop (vertical line of glyphs).
+        let mut glyphs: Vec<Glyph> = self
+            .build_drops(half)
+            .into_iter()
+            .flat_map(|(drop_entropy, column)| {
+                let rain = match &heads_only {
+                    Some((budget, rain)) if build_start.elapsed() > *budget => rain,
+                    _ => self,
+                };
+                rain.build_drop(drop_entropy, column, half.width, half.height, entry, stats)
+            })
+            .collect();
+
+        // The fog is built on the same half, so it's mirrored and scaled along with the drops.
+        glyphs.extend(self.build_fog(half.width, half.height));
+
+        // Sort all the glyphs by priority, so the highest priority glyphs render on top.
+        // By default this is by age, so drop heads always render on top.
+        // This is a moderate bottleneck when the screen is large / there's a lot of glyphs.
+        let sort_start = Instant::now();
+        glyphs.sort_by(|a, b| self.glyph_priority.compare(a, b));
+        stats.sort_time += sort_start.elapsed();
+
+        // Reflect the computed half, then draw each pixel as a block of cells.
+        let glyphs = self.mirror_glyphs(glyphs, grid);
+        let glyphs = self.scale_glyphs(glyphs, area);
+        let glyphs = self.vignette_glyphs(glyphs, area);
+        let glyphs = self.reflect_glyphs(glyphs, full_area);
+        let glyphs = self.quantize_glyphs(glyphs);
+        let glyphs = self.mask_glyphs(glyphs, stats);
+        stats.glyphs += glyphs.len();
+
+        glyphs
+    }
+
+    /// Compute the half of the grid the rain is computed on when mirroring.
+    ///
+    /// With an odd size, the half includes the center column or row.
+    fn mirror_half(&self, grid: Rect) -> Rect {
+        match self.mirror {
+            Some(Axis::Vertical) => Rect::new(0, 0, grid.width.div_ceil(2), grid.height),
+            Some(Axis::Horizontal) => Rect::new(0, 0, grid.width, grid.height.div_ceil(2)),
+            None => grid,
+        }
+    }
+
+    /// Add the reflection of every glyph in the computed half onto the rest of the grid.
+    fn mirror_glyphs(&self, glyphs: Vec<Glyph>, grid: Rect) -> Vec<Glyph> {
+        let Some(axis) = self.mirror else {
+            return glyphs;
+        };
+        let reflect = |glyph: Glyph| match axis {
+            Axis::Vertical => Glyph {
+                x: grid.width - 1 - glyph.x,
+                ..glyph
+            },
+            Axis::Horizontal => Glyph {
+                y: grid.height - 1 - glyph.y,
+                ..glyph
+            },
+        };
+
+        // Reflections never overlap the computed half (besides a center column or row,
+        // which is its own reflection), so they can simply follow it in render order.
+        let reflections: Vec<Glyph> = glyphs
+            .iter()
+            .map(|glyph| (glyph, reflect(*glyph)))
+            .filter(|(glyph, reflection)| (glyph.x, glyph.y) != (reflection.x, reflection.y))
+            .map(|(_, reflection)| reflection)
+            .collect();
+        glyphs.into_iter().chain(reflections).collect()
+    }
+
+    /// Draw each glyph as a block of cells at the pixel scale, cut off at the area's edges.
+    fn scale_glyphs(&self, glyphs: Vec<Glyph>, area: Rect) -> Vec<Glyph> {
+        let scale = self.pixel_scale;
+        if scale <= 1 {
+            return glyphs;
+        }
+        glyphs
+            .into_iter()
+            .flat_map(|glyph| {
+                (0..scale * scale).map(move |i| Glyph {
+                    x: (glyph.x * scale).saturating_add(i % scale),
+                    y: (glyph.y * scale).saturating_add(i / scale),
+                    ..glyph
+                })
+            })
+            .filter(|glyph| glyph.x < area.width && glyph.y < area.height)
+            .collect()
+    }
+
+    /// Split the area into the area the rain falls in, and the height of the reflection.
+    ///
+    /// The reflection is at most half of the area, so it always has rain to reflect.
+    fn reflection_split(&self, area: Rect) -> (Rect, u16) {
+        let height = self
+            .reflection
+            .map_or(0, |(height, _)| height.min(area.height / 2));
+        (Rect::new(0, 0, area.width, area.height - height), height)
+    }
+
+    /// Add a dimmed, vertically flipped copy of the bottom of the rain beneath it.
+    fn reflect_glyphs(&self, glyphs: Vec<Glyph>, area: Rect) -> Vec<Glyph> {
+        let Some((_, dim)) = self.reflection else {
+            return glyphs;
+        };
+        let (rain_area, height) = self.reflection_split(area);
+        let waterline = rain_area.height;
+
+        // Reflections are below every glyph of the rain, so they follow it in render order.
+        let reflections: Vec<Glyph> = glyphs
+            .iter()
+            .filter(|glyph| glyph.y + height >= waterline)
+            .map(|glyph| Glyph {
+                y: 2 * waterline - 1 - glyph.y,
+                style: match glyph.style.fg {
+                    Some(color) => glyph.style.fg(lerp_color(color, Color::Black, dim)),
+                    None => glyph.style,
+                },
+                ..*glyph
+            })
+            .collect();
+        let mut glyphs = glyphs;
+        glyphs.extend(reflections);
+        glyphs
+    }
+
+    /// Quantize the color of each glyph to the color depth.
+    fn quantize_glyphs(&self, glyphs: Vec<Glyph>) -> Vec<Glyph> {
+        if self.color_depth == ColorDepth::TrueColor {
+            return glyphs;
+        }
+        glyphs
+            .into_iter()
+            .map(|glyph| Glyph {
+                style: Style {
+                    fg: glyph.style.fg.map(|fg| self.color_depth.quantize(fg)),
+                    ..glyph.style
+                },
+                ..glyph
+            })
+            .collect()
+    }
+
+    /// Cull every glyph outside the shape mask, if there is one.
+    fn mask_glyphs(&self, glyphs: Vec<Glyph>, stats: &mut RenderStats) -> Vec<Glyph> {
+        let Some(mask) = &self.shape_mask else {
+            return glyphs;
+        };
+        let count = glyphs.len();
+        let glyphs: Vec<Glyph> = glyphs
+            .into_iter()
+            .filter(|glyph| {
+                mask.get(glyph.y as usize)
+                    .and_then(|row| row.get(glyph.x as usize))
+                    .copied()
+                    .unwrap_or(false)
+            })
+            .collect();
+        stats.culled += count - glyphs.len();
+        glyphs
+    }
+
+    /// Dim each glyph toward black by its distance from the center of the area.
+    fn vignette_glyphs(&self, glyphs: Vec<Glyph>, area: Rect) -> Vec<Glyph> {
+        if self.vignette <= 0.0 {
+            return glyphs;
+        }
+        glyphs
+            .into_iter()
+            .map(|glyph| {
+                // Normalize the distance so the center is 0 and the corners are 1.
+                let dx = (glyph.x as f64 + 0.5) / area.width as f64 * 2.0 - 1.0;
+                let dy = (glyph.y as f64 + 0.5) / area.height as f64 * 2.0 - 1.0;
+                let distance = ((dx * dx + dy * dy) / 2.0).sqrt();
+
+                let style = match glyph.style.fg {
+                    Some(color) if to_rgb(color).is_some() => glyph.style.fg(lerp_color(
+                        color,
+                        Color::Rgb(0, 0, 0),
+                        self.vignette * distance,
+                    )),
+                    _ => glyph.style,
+                };
+                Glyph { style, ..glyph }
+            })
+            .collect()
+    }
+
+    /// Compute the grid of pixels the rain is computed on, scaled down from the area.
+    fn pixel_grid(&self, area: Rect) -> Rect {
+        let scale = self.pixel_scale.max(1);
+        Rect::new(
+            0,
+            0,
+            area.width.div_ceil(scale),
+            area.height.div_ceil(scale),
+        )
+    }
+
+    /// Convert an elapsed duration into seconds on the animation clock, shifted by the
+    /// time offset and at the time scale.
+    fn clock_secs(&self, elapsed: Duration) -> f64 {
+        (elapsed + self.time_offset).as_secs_f64() * self.time_scale
+    }
+
+    /// Convert seconds elapsed into seconds of drop motion, accounting for speed pulses.
+    ///
+    /// A pulse multiplies the speed by `1 + a * sin(2πt / p)`. Integrating that gives the
+    /// motion time, which keeps positions continuous as the speed changes.
+    fn motion_secs(&self, secs: f64) -> f64 {
+        match self.speed_pulse {
+            Some((amplitude, period)) if !period.is_zero() => {
+                let amplitude = amplitude.clamp(0.0, 1.0);
+                let angular = 2.0 * std::f64::consts::PI / period.as_secs_f64();
+                secs + amplitude / angular * (1.0 - (angular * secs).cos())
+            }
+            _ => secs,
+        }
+    }
+
+    /// Compute the fraction of drops allowed to spawn a cycle at the given motion time.
+    ///
+    /// Each drop has a stable threshold in [0, 1), and only spawns cycles while it's
+    /// below this fraction. This thins drops out without reshuffling the pattern.
+    fn spawn_fraction(&self, motion_secs: f64, area: Rect) -> f64 {
+        let mut fraction = 1.0;
+
+        // No drops may spawn outside the active window, and they ramp in and out at its edges.
+        if let Some((start, end)) = self.active_window {
+            let start = self.motion_secs(start.as_secs_f64());
+            let end = self.motion_secs(end.as_secs_f64());
+            let ramp = (end - start) * ACTIVE_WINDOW_RAMP;
+            fraction = if motion_secs < start || motion_secs >= end {
+                0.0
+            } else if ramp > 0.0 {
+                ((motion_secs - start) / ramp)
+                    .min((end - motion_secs) / ramp)
+                    .min(1.0)
+            } else {
+                1.0
+            };
+        }
+
+        // Drops are linearly thinned out to nothing over the despawn ramp.
+        if let Some((start, duration)) = self.despawn_ramp {
+            let ramp_start = self.motion_secs(start.as_secs_f64());
+            let ramp_end = self.motion_secs((start + duration).as_secs_f64());
+            fraction *= if ramp_end > ramp_start {
+                (1.0 - (motion_secs - ramp_start) / (ramp_end - ramp_start)).clamp(0.0, 1.0)
+            } else if motion_secs < ramp_start {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        // Drops are thinned out to the current density of the storm cycle.
+        if let Some((period, calm_density, storm_density)) = self.storm_cycle {
+            let drop_len = self.mean_drop_len(area.height);
+            let calm = calm_density.num_drops(area, drop_len) as f64;
+            let storm = storm_density.num_drops(area, drop_len) as f64;
+            if !period.is_zero() && calm.max(storm) > 0.0 {
+                let angle = motion_secs / period.as_secs_f64() * std::f64::consts::TAU;
+                let intensity = (1.0 - angle.cos()) / 2.0;
+                fraction *= (calm + (storm - calm) * intensity) / calm.max(storm);
+            }
+        }
+
+        fraction
     }
 
-    /// Build the rng. Uses a fast but portable and reproducible rng.
-    fn build_rng(&self) -> impl RngCore {
-        Pcg64Mcg::seed_from_u64(self.seed)
+    /// Build the fog of unmoving glyphs behind the drops, if there is one.
+    ///
+    /// Which cells contain fog is fixed by the seed. Fog is infinitely old, so it's drawn
+    /// underneath every drop.
+    fn build_fog(&self, width: u16, height: u16) -> Vec<Glyph> {
+        if self.fog <= 0.0 {
+            return vec![];
+        }
+        let elapsed = self.clock_secs(self.elapsed);
+        let noise_interval = self.noise_interval.as_secs_f64();
+        let fog_seed = mix(self.seed ^ mix(self.instance_offset) ^ FOG_SALT);
+
+        let mut style = Style::default().fg(lerp_color(self.color, Color::Black, 0.5));
+        if self.style_mode == StyleMode::Modifiers {
+            style = style.dim().not_bold();
+        }
+
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let entropy = mix(fog_seed ^ mix(y as u64 * width as u64 + x as u64));
+                if uniform(entropy, 0.0, 1.0) >= self.fog {
+                    return None;
+                }
+
+                // Fog glyphs cycle through the character set like the drops' noise.
+                let character_set = self.character_set_for_row(y);
+                let time_offset = uniform(
+                    mix(entropy),
+                    0.0,
+                    noise_interval * character_set.size() as f64,
+                );
+                let noise_phase = ((time_offset + elapsed) / noise_interval) as u32;
+                Some(Glyph {
+                    x,
+                    y,
+                    age: f64::INFINITY,
+                    content: self.character(&character_set, noise_phase),
+                    style,
+                })
+            })
+            .collect()
     }
 
     /// Build a drop from the given consistent initial entropy state.
     ///
     /// The entropy vector's length becomes the drop's track length, so ensure it's at
-    /// least the window height.
-    fn build_drop(&self, entropy: Vec<u64>, width: u16, height: u16) -> Vec<Glyph> {
-        let elapsed = self.elapsed.as_secs_f64();
-        let rain_speed = self.rain_speed.speed();
+    /// least the window height. If a column is given, the drop stays in it every cycle.
+    fn build_drop(
+        &self,
+        entropy: Vec<u64>,
+        column: Option<u16>,
+        width: u16,
+        height: u16,
+        entry: EntryBounds,
+        stats: &mut RenderStats,
+    ) -> Vec<Glyph> {
+        let elapsed = self.clock_secs(self.elapsed);
+        let motion_elapsed = self.motion_secs(elapsed);
+        let rain_speed = self.base_speed();
         let tail_lifespan = self.tail_lifespan.as_secs_f64();
         let noise_interval = self.noise_interval.as_secs_f64();
+        let area = Rect::new(0, 0, width, height);
+
+        // Compute how many columns the wind moves a glyph per row it falls.
+        // A velocity overrides the wind angle, moving a fixed ratio of columns per row.
+        let wind_slope = match self.velocity {
+            Some((vx, vy)) => vx / vy,
+            None => self.wind_angle.clamp(-80.0, 80.0).to_radians().tan() * self.cell_aspect,
+        };
 
         // A single drop can expect to be called with the exact same entropy vec on each
         // frame. This means we can sample the entropy vec to reproducibly generate
@@ -512,11 +5699,22 @@ impl Rain {
 
         // Use some entropy to compute the drop's actual speed.
         // n.b. since the entropy vec is stable, the drop's speed will not vary over time.
-        let rain_speed = uniform(
-            entropy[0],
-            rain_speed * (1.0 - self.rain_speed_variance),
-            rain_speed * (1.0 + self.rain_speed_variance),
-        )
+        let rain_speed = match self.speed_distribution {
+            SpeedDistribution::Uniform => uniform(
+                entropy[0],
+                rain_speed * (1.0 - self.rain_speed_variance),
+                rain_speed * (1.0 + self.rain_speed_variance),
+            ),
+            SpeedDistribution::Normal { stddev } => {
+                // Box-Muller transform, with a second independent uniform from hashing.
+                let radius = (-2.0 * uniform(entropy[0], f64::EPSILON, 1.0).ln()).sqrt();
+                let angle = uniform(mix(entropy[0] ^ SPEED_SALT), 0.0, std::f64::consts::TAU);
+                rain_speed * (1.0 + stddev * radius * angle.cos())
+            }
+            SpeedDistribution::Exponential => {
+                -rain_speed * uniform(entropy[0], f64::EPSILON, 1.0).ln()
+            }
+        }
         .max(1e-3); // Prevent speed from hitting 0 (if user specifies high variance)
 
         // Compute how long our drop will take to make 1 cycle given our track len and speed
@@ -524,30 +5722,159 @@ impl Rain {
 
         // Use some entropy to compute a stable random time offset for this drop.
         // If this value were 0, every drop would start falling with an identical y value.
-        let initial_cycle_offset_secs = uniform(entropy[0], 0.0, cycle_time_secs);
+        // When decorrelating, hash the entropy so the phase is independent of the speed.
+        let phase_entropy = if self.decorrelate {
+            mix(entropy[0] ^ PHASE_SALT)
+        } else {
+            entropy[0]
+        };
+        let initial_cycle_offset_secs = uniform(phase_entropy, 0.0, cycle_time_secs);
+
+        // Use some entropy to decide how early this drop stops spawning when thinned out.
+        let spawn_threshold = uniform(mix(entropy[0] ^ SPAWN_SALT), 0.0, 1.0);
+
+        // Use some entropy to compute a stable delay before the drop starts falling.
+        let start_delay_secs = self.motion_secs(uniform(
+            mix(entropy[0] ^ DELAY_SALT),
+            0.0,
+            self.start_delay_max.as_secs_f64(),
+        ));
 
         // Compute how far we are into the current cycle and current drop head height.
-        let current_cycle_offset_secs = (elapsed + initial_cycle_offset_secs) % cycle_time_secs;
-        let head_y = (current_cycle_offset_secs * rain_speed) as u16;
+        let current_cycle_offset_secs =
+            (motion_elapsed + initial_cycle_offset_secs) % cycle_time_secs;
+        let head_pos = current_cycle_offset_secs * rain_speed;
+
+        // Compute which cycle the head is in, from the start of its current cycle.
+        // Rounding keeps this consistent with the head's offset into the cycle, where
+        // dividing the elapsed time directly can disagree on the frame the drop wraps.
+        let head_cycle = ((motion_elapsed + initial_cycle_offset_secs - current_cycle_offset_secs)
+            / cycle_time_secs)
+            .round() as usize;
+
+        // Round the head to a row. Rounding to the nearest row shifts it by half a row,
+        // which wraps it into the next cycle at the very end of the track.
+        let (head_pos, head_cycle) = match self.head_rounding {
+            Rounding::Floor => (head_pos, head_cycle),
+            Rounding::Nearest if head_pos + 0.5 >= track_len as f64 => {
+                (head_pos + 0.5 - track_len as f64, head_cycle + 1)
+            }
+            Rounding::Nearest => (head_pos + 0.5, head_cycle),
+        };
+        let head_y = head_pos as u16;
+
+        // With a lifespan, the drop lives for a number of cycles from cycle 1, then goes
+        // dark for a stable gap of 1 up to that many cycles before it's reborn.
+        let lifespan = self.drop_lifespan.map(|cycles| {
+            let gap = 1 + mix(entropy[0] ^ LIFESPAN_SALT) % cycles as u64;
+            (cycles as usize, cycles as usize + gap as usize)
+        });
+        let (life, life_start) = match lifespan {
+            Some((_, period)) if head_cycle > 0 => {
+                let life = (head_cycle - 1) / period;
+                (life, life * period + 1)
+            }
+            _ => (0, 0),
+        };
 
-        // Compute drop length given speed and tail lifespan.
+        // With column drift, the drop's column takes a random step each cycle.
+        // The walk is summed once up to the head's cycle, then stepped back for older cycles.
+        // Each life starts from a fresh column, and the first life matches no lifespan.
+        let drift_step = |cycle: usize, max: i16| {
+            let steps = 2 * max as u64 + 1;
+            (mix(entropy[0] ^ mix(cycle as u64 ^ DRIFT_SALT)) % steps) as i64 - max as i64
+        };
+        let drift = self.column_drift.map(|max| {
+            let start = (mix(entropy[0] ^ DRIFT_SALT ^ mix(life as u64)) % width as u64) as i64;
+            let walk: i64 = (life_start + 1..=head_cycle)
+                .map(|cycle| drift_step(cycle, max))
+                .sum();
+            (max, start + walk)
+        });
+
+        // Compute drop length given speed and tail lifespan, or pick a weighted length.
+        // A computed length is at least 1, so slow drops with short tails still show a head.
         // Cap at screen height to avoid weird wraparound when tail length is long.
-        let drop_len = ((rain_speed * tail_lifespan) as u16).min(height);
+        let drop_len = if self.tail_length_weights.is_empty() {
+            ((rain_speed * tail_lifespan) as u16).max(1)
+        } else {
+            let weights = &self.tail_length_weights;
+            let total: f64 = weights.iter().map(|(_, weight)| weight).sum();
+            let mut pick = uniform(mix(entropy[0] ^ TAIL_SALT), 0.0, total);
+            weights
+                .iter()
+                .find(|(_, weight)| {
+                    pick -= weight;
+                    pick < 0.0
+                })
+                .or(weights.iter().rev().find(|(_, weight)| *weight > 0.0))
+                .map_or(0, |(tail_length, _)| *tail_length)
+        }
+        .min(height);
+
+        // With varying tracks, each cycle's track is shorter than the drop's own by a
+        // random amount, and the head starts that many rows before the top of the screen.
+        // Tracks are never shorter than the screen, so every cycle still reaches the bottom.
+        let track_shift = |cycle: usize| {
+            if !self.varying_tracks {
+                return 0;
+            }
+            let (min_track_len, _) = self.track_len_bounds(height);
+            let min_track_len = (min_track_len as u16).max(height).min(track_len);
+            let spread = (track_len - min_track_len) as u64 + 1;
+            let cycle_entropy = mix(entropy[cycle % entropy.len()] ^ TRACK_SALT);
+            spread as u16 - 1 - (cycle_entropy % spread) as u16
+        };
+        let head_shift = track_shift(head_cycle);
+
+        // When bouncing, the drop takes this many more rows of its track to leave the screen.
+        let bounce_len = if self.bounce {
+            2 * bounce_rise(height)
+        } else {
+            0
+        };
 
         // Render each glyph in the drop.
-        (0..drop_len)
-            .filter_map(|y_offset| {
+        // A head shadow is built like an extra glyph one row behind the head.
+        let shadow = self.head_shadow.filter(|_| drop_len > 0);
+        let mut glyphs: Vec<Glyph> = (0..drop_len)
+            .map(|y_offset| (y_offset, None))
+            .chain(shadow.map(|shadow| (1, Some(shadow))))
+            .filter_map(|(y_offset, shadow)| {
                 // Compute how long ago this glyph would have first appeared
                 let age = y_offset as f64 / rain_speed;
 
                 // If it would have first appeared before the rendering began, don't render.
-                if age > elapsed {
+                if age > motion_elapsed {
+                    return None;
+                }
+
+                // Don't render glyphs beyond the tail cutoff.
+                if shadow.is_none() && y_offset as f64 >= drop_len as f64 * self.tail_cutoff {
+                    return None;
+                }
+
+                // Don't render any of the tail when only rendering heads.
+                if shadow.is_none() && self.heads_only && y_offset > 0 {
+                    return None;
+                }
+
+                // Tails behind the head wrap into the previous cycle at the top of the track.
+                // Tails ahead of the head are cut off at the bottom instead.
+                let ahead = self.tail_direction == TailDirection::Ahead;
+                let wrapped = !ahead && y_offset > head_y;
+
+                // When clipping, the tail never wraps into the previous cycle, and the whole
+                // drop vanishes once the head leaves the screen.
+                if self.wrap_mode == WrapMode::Clip
+                    && (wrapped || head_y.saturating_sub(head_shift) >= height + bounce_len)
+                {
                     return None;
                 }
 
-                // Compute which cycle this particular glyph is a member of
-                let cycle_num =
-                    ((elapsed + initial_cycle_offset_secs - age) / cycle_time_secs) as usize;
+                // Compute which cycle this particular glyph is a member of.
+                // This is derived from rows rather than times, so it always agrees with the row.
+                let cycle_num = head_cycle.saturating_sub(wrapped as usize);
 
                 // Don't render glyphs from cycle 0
                 // (prevents drops from appearing to spawn in the middle of the screen)
@@ -555,108 +5882,1076 @@ impl Rain {
                     return None;
                 }
 
+                // Don't render glyphs from cycles between the drop's lives.
+                if lifespan.is_some_and(|(cycles, period)| (cycle_num - 1) % period >= cycles) {
+                    return None;
+                }
+
+                // Don't render glyphs from cycles this drop wasn't allowed to spawn.
+                // (e.g. outside the active window, or after it's passed the despawn ramp)
+                // A cycle starts when its head reaches the top row.
+                let shift = track_shift(cycle_num);
+                let cycle_start_secs = cycle_num as f64 * cycle_time_secs
+                    - initial_cycle_offset_secs
+                    + shift as f64 / rain_speed;
+                if spawn_threshold >= self.spawn_fraction(cycle_start_secs, area) {
+                    return None;
+                }
+
+                // Don't render glyphs from cycles that began before the drop's start delay.
+                if cycle_start_secs < start_delay_secs {
+                    return None;
+                }
+
                 // Get stable entropy to decide what column cycle X is rendered in.
                 // This must be per-glyph to prevent drops from jumping side-to-side when they wrap around.
-                let x_entropy = entropy[cycle_num % entropy.len()];
-                let x = (x_entropy % width as u64) as u16;
+                // When decorrelating, hash it so the column is independent of the noise.
+                let x_entropy = if self.decorrelate {
+                    mix(entropy[cycle_num % entropy.len()] ^ COLUMN_SALT)
+                } else {
+                    entropy[cycle_num % entropy.len()]
+                };
+                let x = match (column, drift) {
+                    (Some(column), _) => column,
+                    (None, Some((max, walk))) => {
+                        let back: i64 = (cycle_num + 1..=head_cycle)
+                            .map(|cycle| drift_step(cycle, max))
+                            .sum();
+                        (walk - back).rem_euclid(width as i64) as u16
+                    }
+                    (None, None) if self.uniform_columns => uniform_index(x_entropy, width),
+                    (None, None) => (x_entropy % width as u64) as u16,
+                };
+
+                // With top entry only, make sure heads never first appear below the top row.
+                let mut entering = false;
+                if self.top_entry_only {
+                    // Cycles that began before rendering started may already be mid-screen.
+                    let first_secs = self.motion_secs(self.clock_secs(entry.first_elapsed));
+                    if cycle_start_secs < first_secs {
+                        return None;
+                    }
+
+                    // Cycles that began since the last frame only show their head.
+                    entering = entry.last_elapsed.is_some_and(|last_elapsed| {
+                        cycle_start_secs > self.motion_secs(self.clock_secs(last_elapsed))
+                    });
+                    if entering && y_offset > 0 {
+                        return None;
+                    }
+                }
 
                 // Compute the y value for this glyph, and don't render if off the screen.
-                let y = (head_y + track_len - y_offset) % track_len;
+                // Entering heads are pinned to the top row for their first frame.
+                // Bouncing glyphs follow the head's path back up from the bottom.
+                // Glyphs before the start of a shortened track are above the screen.
+                let y = if entering {
+                    0
+                } else if self.bounce {
+                    let pos = if ahead {
+                        head_pos + y_offset as f64
+                    } else {
+                        (head_pos - y_offset as f64).rem_euclid(track_len as f64)
+                    };
+                    if pos < shift as f64 {
+                        return None;
+                    }
+                    bounce_y(pos - shift as f64, height)
+                } else {
+                    let pos = if ahead {
+                        head_y.saturating_add(y_offset)
+                    } else {
+                        (head_y + track_len - y_offset) % track_len
+                    };
+                    pos.checked_sub(shift)?
+                };
                 if y >= height {
                     return None;
                 }
 
+                // Slant the glyph by the wind, wrapping around the sides of the screen.
+                // The offset is accumulated as a float so small angles still slant.
+                let x = (x as f64 + y as f64 * wind_slope).round() as i64;
+                let x = x.rem_euclid(width as i64) as u16;
+
+                // Rising drops follow the same path flipped upside down, so the wind still
+                // blows them the same way.
+                let y = match self.direction {
+                    RainDirection::Down => y,
+                    RainDirection::Up => height - 1 - y,
+                };
+
+                // Use the character set for this row, if it varies by row.
+                let character_set = self.character_set_for_row(y);
+
                 // The 'noise' of glyphs randomly changing is actually modeled as every glyph in the track
                 // just cycling through possible values veeeery slowly. We need a random offset for this
                 // cycling so every glyph doesn't change at the same time.
                 let time_offset = uniform(
                     entropy[y as usize],
                     0.0,
-                    noise_interval * self.character_set.size() as f64,
+                    noise_interval * character_set.size() as f64,
                 );
 
-                // Decide what character is rendered based on noise.
-                let content = self
-                    .character_set
-                    .get(((time_offset + elapsed) / noise_interval) as u32);
+                // Compute the current phase of the noise cycle for this glyph.
+                let noise_time = match self.noise_driver {
+                    NoiseDriver::Elapsed => elapsed,
+                    NoiseDriver::Progress => current_cycle_offset_secs,
+                };
+                let noise_phase = ((time_offset + noise_time) / noise_interval) as u32;
+
+                // Decide what character is rendered based on noise, unless noise is only
+                // driving brightness, in which case the character stays at its initial phase.
+                // Falling glyphs ignore the noise, and are fixed for this cell and cycle.
+                let mut content = if self.falling_glyphs {
+                    let cycle_entropy = mix(cycle_num as u64 ^ GLYPH_SALT);
+                    self.character(
+                        &character_set,
+                        mix(entropy[y as usize] ^ cycle_entropy) as u32,
+                    )
+                } else {
+                    match self.noise_mode {
+                        NoiseMode::Character => self.character(&character_set, noise_phase),
+                        NoiseMode::Brightness => {
+                            self.character(&character_set, (time_offset / noise_interval) as u32)
+                        }
+                    }
+                };
+
+                // Render the head as the braille dots nearest its fractional position.
+                if self.subcell == SubCell::Braille && y_offset == 0 {
+                    let sub_row = if entering {
+                        0
+                    } else {
+                        ((head_pos.fract() * 4.0) as usize).min(3)
+                    };
+                    content = match self.direction {
+                        RainDirection::Down => BRAILLE_ROWS[sub_row],
+                        RainDirection::Up => BRAILLE_ROWS[3 - sub_row],
+                    };
+                }
 
                 // Compute the styling for the glyph
                 let mut style = Style::default();
 
+                // Gradients run from the head to the end of the tail, unless inverted.
+                let gradient_offset = if self.invert_gradient {
+                    drop_len.saturating_sub(1).saturating_sub(y_offset)
+                } else {
+                    y_offset
+                };
+
                 // Color appropriately depending on whether this glyph is the head.
-                if age > 0.0 {
+                // Color stops and gradients override both, and color by position instead.
+                // Shadows override all of these with their own color.
+                if let Some((_, color)) = shadow {
+                    style = style.fg(color)
+                } else if !self.color_stops.is_empty() {
+                    let color =
+                        color_at_stops(&self.color_stops, gradient_offset as f64 / drop_len as f64);
+                    style = style.fg(brighten_to(color, self.min_brightness))
+                } else if let Some((start, end)) = self.indexed_gradient {
+                    style = style.fg(Color::Indexed(lerp_indexed(
+                        start,
+                        end,
+                        gradient_offset as f64 / drop_len as f64,
+                    )))
+                } else if age > 0.0 {
                     style = style.fg(self.color)
                 } else {
                     style = style.fg(self.head_color)
                 }
 
-                // The lowest third of glyphs is bold, the highest third is dim
-                if self.bold_dim_effect {
-                    if y_offset < drop_len / 3 {
+                // When debugging, color by progress through the cycle instead, from cold to hot.
+                if self.debug_age_coloring {
+                    let progress = current_cycle_offset_secs / cycle_time_secs;
+                    style = style.fg(lerp_color(
+                        Color::Rgb(0, 0, 255),
+                        Color::Rgb(255, 0, 0),
+                        progress,
+                    ));
+                }
+
+                // Brightness noise cycles glyphs through bold, normal, and dim.
+                // Otherwise the lowest third of glyphs is bold, the highest third is dim,
+                // or the other way around when the gradient is inverted.
+                // Absolute numbers of bold or dim rows override the thirds.
+                // Neither applies when styling by color only.
+                let modifiers = self.style_mode == StyleMode::Modifiers;
+                if modifiers && self.noise_mode == NoiseMode::Brightness {
+                    style = match noise_phase % 3 {
+                        0 => style.bold().not_dim(),
+                        1 => style.not_bold().not_dim(),
+                        _ => style.dim().not_bold(),
+                    }
+                } else if modifiers && self.bold_dim_effect {
+                    let bold = match self.bold_rows {
+                        Some(bold_rows) => gradient_offset < bold_rows,
+                        None => gradient_offset < drop_len / 3,
+                    };
+                    let dim = match self.dim_rows {
+                        Some(dim_rows) => gradient_offset + dim_rows >= drop_len,
+                        None => gradient_offset > drop_len * 2 / 3,
+                    };
+                    if bold {
                         style = style.bold().not_dim()
-                    } else if y_offset > drop_len * 2 / 3 {
+                    } else if dim {
                         style = style.dim().not_bold()
                     } else {
                         style = style.not_bold().not_dim()
                     }
                 }
 
-                Some(Glyph {
-                    x,
-                    y,
-                    age,
-                    content,
-                    style,
-                })
+                // Occasionally glitch the glyph into reverse video for a moment.
+                if self.glitch > 0.0 {
+                    let step = (elapsed / GLITCH_SECS) as u64;
+                    let glitch_entropy = mix(entropy[y as usize] ^ mix(step ^ GLITCH_SALT));
+                    if uniform(glitch_entropy, 0.0, 1.0) < self.glitch {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                }
+
+                // Shadows rank just below the head, halfway to the next glyph of the tail.
+                match shadow {
+                    Some((shadow, _)) => Some(Glyph {
+                        x,
+                        y,
+                        age: 0.5 / rain_speed,
+                        content: shadow,
+                        style,
+                    }),
+                    None => Some(Glyph {
+                        x,
+                        y,
+                        age,
+                        content,
+                        style,
+                    }),
+                }
             })
-            .collect()
+            .collect();
+
+        // Track how many of the drop's glyphs weren't rendered.
+        stats.culled +=
+            (drop_len as usize + shadow.is_some() as usize).saturating_sub(glyphs.len());
+
+        // Occasionally throw off sparkles from the tail into a neighboring column.
+        // Each sparkle is re-rolled every time the drop moves a row, so they're short-lived.
+        if let Some((rate, color)) = self.sparkle {
+            let step = (motion_elapsed * rain_speed) as u64;
+            let sparkles: Vec<Glyph> = glyphs
+                .iter()
+                .filter(|glyph| glyph.age > 0.0)
+                .filter_map(|glyph| {
+                    let sparkle_entropy = mix(entropy[glyph.y as usize] ^ mix(step ^ SPARKLE_SALT));
+                    if uniform(sparkle_entropy, 0.0, 1.0) >= rate {
+                        return None;
+                    }
+                    let offset = if sparkle_entropy & 1 == 0 { -1 } else { 1 };
+                    let mut style = Style::default().fg(color);
+                    if self.style_mode == StyleMode::Modifiers {
+                        style = style.bold().not_dim();
+                    }
+                    Some(Glyph {
+                        x: (glyph.x as i64 + offset).rem_euclid(width as i64) as u16,
+                        content: self
+                            .character_set_for_row(glyph.y)
+                            .get((sparkle_entropy >> 32) as u32),
+                        style,
+                        ..*glyph
+                    })
+                })
+                .collect();
+            glyphs.extend(sparkles);
+        }
+
+        glyphs
+    }
+}
+
+/// Rain is hashed field by field, with floats hashed by their bit patterns. This allows
+/// using a configuration (including its elapsed duration) as a cache key:
+///
+/// ```
+/// use std::{collections::HashMap, time::Duration};
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// use tui_rain::Rain;
+///
+/// let area = Rect::new(0, 0, 20, 10);
+/// let mut cache: HashMap<Rain, Buffer> = HashMap::new();
+///
+/// let rain = Rain::new_matrix(Duration::from_secs(5)).with_wind_angle(15.0);
+/// let mut buf = Buffer::empty(area);
+/// rain.clone().render(area, &mut buf);
+/// cache.insert(rain, buf);
+///
+/// let same = Rain::new_matrix(Duration::from_secs(5)).with_wind_angle(15.0);
+/// assert!(cache.contains_key(&same));
+/// ```
+///
+/// Equality still compares floats by value, so a configuration containing `NaN` is not
+/// equal to itself.
+impl Hash for Rain {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Destructure so that new fields can't be forgotten here.
+        let Rain {
+            elapsed,
+            seed,
+            rain_density,
+            rain_speed,
+            rain_speed_variance,
+            tail_lifespan,
+            color,
+            head_color,
+            bold_dim_effect,
+            noise_interval,
+            character_set,
+            noise_mode,
+            wrap_mode,
+            cell_aspect,
+            decorrelate,
+            tail_cutoff,
+            active_window,
+            color_stops,
+            no_overlap,
+            indexed_gradient,
+            wind_angle,
+            speed_pulse,
+            rng_kind,
+            track_length_range,
+            instance_offset,
+            top_entry_only,
+            despawn_ramp,
+            subcell,
+            start_delay_max,
+            glyph_priority,
+            noise_driver,
+            max_drops,
+            blend,
+            min_brightness,
+            pixel_scale,
+            falling_glyphs,
+            ghost_trail,
+            time_scale,
+            transparent_chars,
+            scanline_output,
+            uniform_columns,
+            speed_distribution,
+            sparkle,
+            mirror,
+            time_offset,
+            heads_only,
+            style_mode,
+            storm_cycle,
+            character_set_by_row,
+            min_frame_interval,
+            vignette,
+            bounce,
+            shuffled_charset,
+            velocity,
+            column_drift,
+            cell_merge,
+            clear,
+            tail_length_weights,
+            debug_age_coloring,
+            head_shadow,
+            shape_mask,
+            head_rounding,
+            tail_direction,
+            color_depth,
+            invert_gradient,
+            time_budget,
+            fog,
+            varying_tracks,
+            glitch,
+            min_drops,
+            reflection,
+            drop_lifespan,
+            bold_rows,
+            dim_rows,
+            direction,
+        } = self;
+        elapsed.hash(state);
+        seed.hash(state);
+        rain_density.hash(state);
+        std::mem::discriminant(rain_speed).hash(state);
+        hash_f64(rain_speed.speed(1.0), state);
+        hash_f64(*rain_speed_variance, state);
+        tail_lifespan.hash(state);
+        color.hash(state);
+        head_color.hash(state);
+        bold_dim_effect.hash(state);
+        noise_interval.hash(state);
+        character_set.hash(state);
+        noise_mode.hash(state);
+        wrap_mode.hash(state);
+        hash_f64(*cell_aspect, state);
+        decorrelate.hash(state);
+        hash_f64(*tail_cutoff, state);
+        active_window.hash(state);
+        color_stops.len().hash(state);
+        for (position, color) in color_stops {
+            hash_f64(*position, state);
+            color.hash(state);
+        }
+        no_overlap.hash(state);
+        indexed_gradient.hash(state);
+        hash_f64(*wind_angle, state);
+        if let Some((amplitude, period)) = speed_pulse {
+            hash_f64(*amplitude, state);
+            period.hash(state);
+        }
+        rng_kind.hash(state);
+        hash_f64(track_length_range.0, state);
+        hash_f64(track_length_range.1, state);
+        instance_offset.hash(state);
+        top_entry_only.hash(state);
+        despawn_ramp.hash(state);
+        subcell.hash(state);
+        start_delay_max.hash(state);
+        glyph_priority.hash(state);
+        noise_driver.hash(state);
+        max_drops.hash(state);
+        hash_f64(*blend, state);
+        hash_f64(*min_brightness, state);
+        pixel_scale.hash(state);
+        falling_glyphs.hash(state);
+        ghost_trail.hash(state);
+        hash_f64(*time_scale, state);
+        transparent_chars.hash(state);
+        scanline_output.hash(state);
+        uniform_columns.hash(state);
+        std::mem::discriminant(speed_distribution).hash(state);
+        if let SpeedDistribution::Normal { stddev } = speed_distribution {
+            hash_f64(*stddev, state);
+        }
+        if let Some((rate, color)) = sparkle {
+            hash_f64(*rate, state);
+            color.hash(state);
+        }
+        mirror.hash(state);
+        time_offset.hash(state);
+        heads_only.hash(state);
+        style_mode.hash(state);
+        storm_cycle.hash(state);
+        character_set_by_row.hash(state);
+        min_frame_interval.hash(state);
+        hash_f64(*vignette, state);
+        bounce.hash(state);
+        shuffled_charset.hash(state);
+        if let Some((vx, vy)) = velocity {
+            hash_f64(*vx, state);
+            hash_f64(*vy, state);
+        }
+        column_drift.hash(state);
+        cell_merge.hash(state);
+        clear.hash(state);
+        tail_length_weights.len().hash(state);
+        for (tail_length, weight) in tail_length_weights {
+            tail_length.hash(state);
+            hash_f64(*weight, state);
+        }
+        debug_age_coloring.hash(state);
+        head_shadow.hash(state);
+        shape_mask.hash(state);
+        head_rounding.hash(state);
+        tail_direction.hash(state);
+        color_depth.hash(state);
+        invert_gradient.hash(state);
+        time_budget.hash(state);
+        hash_f64(*fog, state);
+        varying_tracks.hash(state);
+        hash_f64(*glitch, state);
+        min_drops.hash(state);
+        if let Some((height, dim)) = reflection {
+            height.hash(state);
+            hash_f64(*dim, state);
+        }
+        drop_lifespan.hash(state);
+        bold_rows.hash(state);
+        dim_rows.hash(state);
+        direction.hash(state);
     }
 }
 
+impl Eq for Rain {}
+
+/// Glyphs are positioned relative to the origin of the area, so the rain stays within
+/// an inset area:
+///
+/// ```
+/// use std::time::Duration;
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// use tui_rain::Rain;
+///
+/// let mut buf = Buffer::empty(Rect::new(0, 0, 80, 24));
+/// let area = Rect::new(10, 5, 20, 10);
+///
+/// Rain::new_matrix(Duration::from_secs(5)).render(area, &mut buf);
+///
+/// let inside = |i: usize| area.contains(buf.pos_of(i).into());
+/// let cells = || buf.content().iter().enumerate();
+/// assert!(cells().any(|(i, cell)| inside(i) && cell.symbol() != " "));
+/// assert!(cells().all(|(i, cell)| inside(i) || cell.symbol() == " "));
+/// ```
 impl Widget for Rain {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let mut rng = self.build_rng();
+        self.render_with_stats(area, buf);
+    }
+}
 
-        // We don't actually have n drops with tracks equal to the screen height.
-        // We actually have 2n drops with tracks ranging from 1.5 to 2.5 the screen height.
-        // This introduces more randomness to the apparent n and reduces cyclic appearance.
-        let num_drops = self.rain_density.num_drops(area) * 2;
-        let drop_track_lens: Vec<usize> = (0..num_drops)
-            .map(|_| (area.height as u64 * 3 / 2 + rng.next_u64() % area.height as u64) as usize)
-            .collect();
+/// A frame of rain computed ahead of time, from [`Rain::precompute`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct PrecomputedFrame {
+    rain: Rain,
+    area: Rect,
+    glyphs: Vec<Glyph>,
+}
 
-        // We construct entropy consistently every frame to mimic statefulness.
-        // This is not a performance bottleneck, so caching wouldn't deliver much benefit.
-        let entropy: Vec<Vec<u64>> = drop_track_lens
-            .iter()
-            .map(|track_len| (0..*track_len).map(|_| rng.next_u64()).collect())
-            .collect();
+impl PrecomputedFrame {
+    /// Write the frame into a buffer, at the area it was computed for.
+    pub fn render(&self, buf: &mut Buffer) {
+        self.rain.clear_area(self.area, buf);
+        self.rain.write_glyphs(self.glyphs.clone(), self.area, buf);
+    }
 
-        // For every entropy vec, construct a single drop (vertical line of glyphs).
-        let mut glyphs: Vec<Glyph> = entropy
-            .into_iter()
-            .flat_map(|drop_entropy| self.build_drop(drop_entropy, area.width, area.height))
-            .collect();
+    /// The area the frame was computed for.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
 
-        // Sort all the glyphs by age so drop heads always render on top.
-        // This is a moderate bottleneck when the screen is large / there's a lot of glyphs.
-        glyphs.sort_by(|a, b| a.age.partial_cmp(&b.age).unwrap_or(Ordering::Equal));
+    /// The glyphs in the frame, in render order.
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+}
 
-        // Actually render to the buffer.
-        for glyph in glyphs {
-            buf[(glyph.x, glyph.y)].set_char(glyph.content);
-            buf[(glyph.x, glyph.y)].set_style(glyph.style);
+/// Estimated statistics about a rain configuration, from [`Rain::preview`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct RainStats {
+    /// The target number of drops on screen.
+    pub num_drops: usize,
+
+    /// The average time for a drop to complete one cycle of its track.
+    pub average_cycle_time: Duration,
+
+    /// The expected number of glyphs on screen in a frame.
+    pub expected_glyphs: usize,
+}
+
+/// Statistics about a rendered frame, from [`Rain::render_with_stats`].
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct RenderStats {
+    /// The number of glyphs drawn to the buffer, including any drawn over each other.
+    pub glyphs: usize,
+
+    /// The number of glyphs in drops that were culled (e.g. for being off the screen).
+    pub culled: usize,
+
+    /// The time spent sorting glyphs to decide which render on top.
+    pub sort_time: Duration,
+}
+
+impl StatefulWidget for Rain {
+    type State = RainState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let (rain, entry) = self.with_state(state);
+        rain.render_with_ghosts(area, buf, state, entry)
+    }
+}
+
+/// Persistent state for rendering a [`Rain`] as a [`StatefulWidget`].
+///
+/// The rain itself is stateless, but pausing without a visible jump on resume requires
+/// remembering how long the animation was paused for. The state tracks this, and
+/// subtracts the total paused time from the elapsed duration of each rendered widget:
+///
+/// ```
+/// use std::time::Duration;
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+/// use tui_rain::{Rain, RainState};
+///
+/// let area = Rect::new(0, 0, 20, 10);
+/// let mut buf = Buffer::empty(area);
+/// let mut state = RainState::default();
+///
+/// state.pause();
+/// Rain::new_matrix(Duration::from_secs(2)).render(area, &mut buf, &mut state);
+/// Rain::new_matrix(Duration::from_secs(5)).render(area, &mut buf, &mut state);
+///
+/// state.resume();
+/// Rain::new_matrix(Duration::from_secs(6)).render(area, &mut buf, &mut state);
+///
+/// assert_eq!(state.paused_duration(), Duration::from_secs(4));
+/// ```
+///
+/// Pausing and resuming take effect on the next render, since that is when the state
+/// learns the current elapsed duration. The elapsed duration given to the widget (at
+/// construction or via [`Rain::with_elapsed`]) should keep advancing while paused.
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug)]
+pub struct RainState {
+    paused: bool,
+    paused_at: Option<Duration>,
+    paused_total: Duration,
+    first_elapsed: Option<Duration>,
+    last_elapsed: Option<Duration>,
+    ghost_area: Rect,
+    ghost_cells: Vec<Option<(Duration, char)>>,
+    last_frame: Option<Frame>,
+    last_buffer: Option<Buffer>,
+}
+
+/// A computed frame, remembered to honor a minimum frame interval.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct Frame {
+    config: u64,
+    area: Rect,
+    elapsed: Duration,
+    glyphs: Vec<(u16, u16, char, Style)>,
+}
+
+/// When rendering with a state began and when its previous frame was, for top entry only.
+///
+/// Rendering without a state begins at the start of the animation, with no previous frame.
+#[derive(Copy, Clone, Default, Eq, PartialEq, Hash, Debug)]
+struct EntryBounds {
+    first_elapsed: Duration,
+    last_elapsed: Option<Duration>,
+}
+
+impl RainState {
+    /// Freeze the animation at the frame currently displayed.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue the animation from where it was paused.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Whether the animation is currently paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// The total duration the animation has spent paused, excluding any ongoing pause.
+    pub fn paused_duration(&self) -> Duration {
+        self.paused_total
+    }
+
+    /// Update the pause tracking and compute the elapsed duration to actually render.
+    fn effective_elapsed(&mut self, elapsed: Duration) -> Duration {
+        match (self.paused, self.paused_at) {
+            (true, None) => self.paused_at = Some(elapsed),
+            (false, Some(paused_at)) => {
+                self.paused_total += elapsed.saturating_sub(paused_at);
+                self.paused_at = None;
+            }
+            _ => {}
         }
+        self.paused_at
+            .unwrap_or(elapsed)
+            .saturating_sub(self.paused_total)
+    }
+}
+
+/// A clock for the rain that only moves when advanced, for deterministic tests.
+///
+/// Rather than tracking a wall clock, advance the clock by a fixed step each frame and
+/// render the rain at the clock's elapsed duration:
+///
+/// ```
+/// use std::time::Duration;
+/// use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+/// use tui_rain::{Rain, RainClock};
+///
+/// let area = Rect::new(0, 0, 20, 10);
+/// let rain = Rain::new_matrix(Duration::ZERO);
+/// let mut clock = RainClock::new();
+///
+/// let mut frames = vec![];
+/// for _ in 0..60 {
+///     clock.advance(Duration::from_millis(100));
+///     let mut buf = Buffer::empty(area);
+///     rain.clone().with_clock(&clock).render(area, &mut buf);
+///     frames.push(buf);
+/// }
+///
+/// // Every frame is exactly reproducible from the time it was stepped to.
+/// let mut buf = Buffer::empty(area);
+/// rain.with_elapsed(Duration::from_secs(6)).render(area, &mut buf);
+/// assert_eq!(frames[59], buf);
+/// ```
+#[derive(Copy, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct RainClock {
+    elapsed: Duration,
+}
+
+impl RainClock {
+    /// Create a clock starting at zero elapsed.
+    pub fn new() -> RainClock {
+        RainClock::default()
+    }
+
+    /// Move the clock forward by a step.
+    pub fn advance(&mut self, step: Duration) {
+        self.elapsed += step;
+    }
+
+    /// The total duration the clock has been advanced by.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
     }
 }
 
 /// A Glyph to be rendered on the screen.
-struct Glyph {
-    x: u16,
-    y: u16,
-    age: f64,
-    content: char,
-    style: Style,
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Glyph {
+    /// The column of the glyph, relative to the rendered area.
+    pub x: u16,
+
+    /// The row of the glyph, relative to the rendered area.
+    pub y: u16,
+
+    /// How long ago the glyph first appeared in seconds. Drop heads have an age of 0.
+    pub age: f64,
+
+    /// The character of the glyph.
+    pub content: char,
+
+    /// The style of the glyph.
+    pub style: Style,
+}
+
+impl Glyph {
+    /// Estimate how bright the glyph appears, from its color and modifiers.
+    fn brightness(&self) -> f64 {
+        let luminance = luminance(self.style.fg.and_then(to_rgb).unwrap_or((192, 192, 192)));
+        let modifiers = self.style.add_modifier - self.style.sub_modifier;
+        if modifiers.contains(Modifier::BOLD) {
+            luminance * 1.5
+        } else if modifiers.contains(Modifier::DIM) {
+            luminance * 0.5
+        } else {
+            luminance
+        }
+    }
+}
+
+/// Which glyph is drawn on top when glyphs overlap.
+#[derive(Copy, Clone, Debug)]
+pub enum Priority {
+    /// The glyph that appeared most recently is drawn on top. Drop heads are newest.
+    Newest,
+
+    /// The glyph that appears brightest, by color luminance and bold / dim, is on top.
+    Brightest,
+
+    /// A custom comparator, where the glyph that compares greater is drawn on top.
+    Custom(fn(&Glyph, &Glyph) -> Ordering),
+}
+
+impl Priority {
+    /// Compare two glyphs, where the greater glyph is drawn on top.
+    fn compare(&self, a: &Glyph, b: &Glyph) -> Ordering {
+        match self {
+            Priority::Newest => b.age.partial_cmp(&a.age).unwrap_or(Ordering::Equal),
+            Priority::Brightest => a
+                .brightness()
+                .partial_cmp(&b.brightness())
+                .unwrap_or(Ordering::Equal),
+            Priority::Custom(compare) => compare(a, b),
+        }
+    }
+}
+
+impl PartialEq for Priority {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Priority::Newest, Priority::Newest) => true,
+            (Priority::Brightest, Priority::Brightest) => true,
+            (Priority::Custom(a), Priority::Custom(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => false,
+        }
+    }
+}
+
+impl Hash for Priority {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let Priority::Custom(compare) = self {
+            (*compare as usize).hash(state);
+        }
+    }
+}
+
+/// Hash a float by its bit pattern, treating both zeroes as equal.
+fn hash_f64<H: Hasher>(value: f64, state: &mut H) {
+    let value = if value == 0.0 { 0.0 } else { value };
+    value.to_bits().hash(state);
+}
+
+/// Compute the color at a position along a list of sorted color stops.
+fn color_at_stops(color_stops: &[(f64, Color)], position: f64) -> Color {
+    // Find the first stop at or after the position, and clamp outside the stops.
+    let next = color_stops
+        .iter()
+        .position(|(pos, _)| *pos >= position)
+        .unwrap_or(color_stops.len() - 1);
+    if next == 0 || color_stops[next].0 < position {
+        return color_stops[next].1;
+    }
+
+    // Interpolate between the stops either side of the position.
+    let (lower_pos, lower) = color_stops[next - 1];
+    let (upper_pos, upper) = color_stops[next];
+    if upper_pos <= lower_pos {
+        return upper;
+    }
+    lerp_color(
+        lower,
+        upper,
+        (position - lower_pos) / (upper_pos - lower_pos),
+    )
+}
+
+/// Linearly interpolate between two colors by `t` in [0, 1].
+///
+/// Colors without an RGB equivalent snap to whichever color is nearer instead.
+fn lerp_color(a: Color, b: Color, t: f64) -> Color {
+    match (to_rgb(a), to_rgb(b)) {
+        (Some(a), Some(b)) => {
+            let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+            Color::Rgb(channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2))
+        }
+        _ if t < 0.5 => a,
+        _ => b,
+    }
+}
+
+/// Compute how many rows a drop rises when bouncing off the bottom of the screen.
+fn bounce_rise(height: u16) -> u16 {
+    height / 6
+}
+
+/// Map a position along a drop's track to a row, bouncing once off the bottom of the screen.
+///
+/// The drop falls to the bottom row, eases back up and down along a parabola, then
+/// continues below the screen.
+fn bounce_y(pos: f64, height: u16) -> u16 {
+    let bottom = height.saturating_sub(1) as f64;
+    let rise = bounce_rise(height) as f64;
+    if pos < bottom {
+        pos as u16
+    } else if pos < bottom + 2.0 * rise {
+        let t = (pos - bottom) / rise - 1.0;
+        (bottom - rise * (1.0 - t * t)).round() as u16
+    } else {
+        (pos - 2.0 * rise) as u16
+    }
+}
+
+/// Parse a hex color of six hex digits, optionally preceded by a `#`.
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Brighten a color toward white until its luminance reaches at least `min` in [0, 1].
+///
+/// Colors without an RGB equivalent are left unchanged.
+fn brighten_to(color: Color, min: f64) -> Color {
+    match to_rgb(color) {
+        Some(rgb) if luminance(rgb) < min => {
+            // Luminance is linear in the channels, so this lerp lands exactly on the floor.
+            let t = (min - luminance(rgb)) / (1.0 - luminance(rgb));
+            lerp_color(color, Color::Rgb(255, 255, 255), t)
+        }
+        _ => color,
+    }
+}
+
+/// Compute the relative luminance of an RGB color, in [0, 1].
+fn luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    (0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64) / 255.0
+}
+
+/// Interpolate between two indexed colors by `t` in [0, 1], walking the 256 color palette.
+fn lerp_indexed(start: u8, end: u8, t: f64) -> u8 {
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+    match (start, end) {
+        // Walk the color cube coordinate by coordinate.
+        (16..=231, 16..=231) => {
+            let (a, b) = (start - 16, end - 16);
+            16 + lerp(a / 36, b / 36) * 36 + lerp(a / 6 % 6, b / 6 % 6) * 6 + lerp(a % 6, b % 6)
+        }
+        // Walk the grayscale ramp directly.
+        (232..=255, 232..=255) => lerp(start, end),
+        // Otherwise interpolate in RGB and find the nearest indexed color.
+        _ => match lerp_color(Color::Indexed(start), Color::Indexed(end), t) {
+            Color::Rgb(r, g, b) => nearest_indexed((r, g, b)),
+            _ => unreachable!("indexed colors always convert to RGB"),
+        },
+    }
+}
+
+/// Find the nearest color in the color cube or grayscale ramp of the 256 color palette.
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    (16..=255)
+        .min_by_key(|i| color_distance(rgb, to_rgb(Color::Indexed(*i)).unwrap()))
+        .unwrap()
+}
+
+/// The squared euclidean distance between two RGB colors.
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Convert a color to RGB, using the standard xterm values for named and indexed colors.
+fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let rgb = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black | Color::Indexed(0) => (0, 0, 0),
+        Color::Red | Color::Indexed(1) => (128, 0, 0),
+        Color::Green | Color::Indexed(2) => (0, 128, 0),
+        Color::Yellow | Color::Indexed(3) => (128, 128, 0),
+        Color::Blue | Color::Indexed(4) => (0, 0, 128),
+        Color::Magenta | Color::Indexed(5) => (128, 0, 128),
+        Color::Cyan | Color::Indexed(6) => (0, 128, 128),
+        Color::Gray | Color::Indexed(7) => (192, 192, 192),
+        Color::DarkGray | Color::Indexed(8) => (128, 128, 128),
+        Color::LightRed | Color::Indexed(9) => (255, 0, 0),
+        Color::LightGreen | Color::Indexed(10) => (0, 255, 0),
+        Color::LightYellow | Color::Indexed(11) => (255, 255, 0),
+        Color::LightBlue | Color::Indexed(12) => (0, 0, 255),
+        Color::LightMagenta | Color::Indexed(13) => (255, 0, 255),
+        Color::LightCyan | Color::Indexed(14) => (0, 255, 255),
+        Color::White | Color::Indexed(15) => (255, 255, 255),
+        // The 6x6x6 color cube
+        Color::Indexed(i @ 16..=231) => {
+            let level = |v: u8| if v == 0 { 0 } else { v * 40 + 55 };
+            let i = i - 16;
+            (level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        // The grayscale ramp
+        Color::Indexed(i) => {
+            let v = (i - 232) * 10 + 8;
+            (v, v, v)
+        }
+    };
+    Some(rgb)
+}
+
+/// The fraction of the active window at each edge over which the rain ramps in and out.
+const ACTIVE_WINDOW_RAMP: f64 = 0.1;
+
+/// The number of glyphs in one frame above which rendering is warned to be slow.
+#[cfg(feature = "log")]
+const SLOW_GLYPH_COUNT: usize = 100_000;
+
+/// The number of recent slow configurations remembered, so each is only warned once.
+#[cfg(feature = "log")]
+const WARNED_CONFIGS: usize = 16;
+
+/// Braille characters with both dots filled in each of the 4 rows, from top to bottom.
+const BRAILLE_ROWS: [char; 4] = ['\u{2809}', '\u{2812}', '\u{2824}', '\u{28C0}'];
+
+// Salts are mixed into a drop's entropy or the seed before hashing it, so that every
+// feature derived from the same random value is independent of the others.
+
+/// The second uniform of a normally distributed speed.
+const SPEED_SALT: u64 = 0x94D0_49BB_1331_11EB;
+/// When tail glyphs sparkle.
+const SPARKLE_SALT: u64 = 0xBF58_476D_1CE4_E5B9;
+/// A drop's phase.
+const PHASE_SALT: u64 = 0x9E37_79B9_7F4A_7C15;
+/// When a drop stops spawning.
+const SPAWN_SALT: u64 = 0x8CB9_2BA7_2F3D_8DD7;
+/// A drop's start delay.
+const DELAY_SALT: u64 = 0xA076_1D64_78BD_642F;
+/// A falling glyph's character.
+const GLYPH_SALT: u64 = 0xE703_7ED1_A0B4_28DB;
+/// A drop's column.
+const COLUMN_SALT: u64 = 0xD1B5_4A32_D192_ED03;
+/// A drop's drifting column.
+const DRIFT_SALT: u64 = 0x2545_F491_4F6C_DD1D;
+/// A drop's weighted tail length.
+const TAIL_SALT: u64 = 0x6C8E_9CF5_7093_2BD5;
+/// A cycle's varying track length.
+const TRACK_SALT: u64 = 0x27D4_EB2F_1656_67C5;
+/// When glyphs glitch into reverse video.
+const GLITCH_SALT: u64 = 0x85EB_CA77_C2B2_AE63;
+/// The gap between a drop's lives.
+const LIFESPAN_SALT: u64 = 0x4CF5_AD43_2745_937F;
+/// The shuffled order of the character set.
+const SHUFFLE_SALT: u64 = 0xC2B2_AE3D_27D4_EB4F;
+/// Which cells contain fog.
+const FOG_SALT: u64 = 0x1656_67B1_9E37_79F9;
+
+/// How long a glitch lasts, in seconds.
+const GLITCH_SECS: f64 = 0.05;
+
+/// Hash bytes into a u64 with 64-bit FNV-1a, which is stable everywhere.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Hash a u64 into a statistically independent u64, using the splitmix64 finalizer.
+fn mix(seed: u64) -> u64 {
+    let mut z = seed;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Permute an index in the range [0, len) to another index in that range, keyed by `key`.
+///
+/// This is a small Feistel network on just enough bits to hold `len`. Results outside the
+/// range are permuted again until they land back in it, which keeps it a bijection.
+fn permute_index(index: u32, len: u32, key: u64) -> u32 {
+    if len <= 1 {
+        return 0;
+    }
+    let half_bits = (u32::BITS - (len - 1).leading_zeros()).div_ceil(2);
+    let half_mask = (1u64 << half_bits) - 1;
+    let mut x = (index % len) as u64;
+    loop {
+        let (mut left, mut right) = (x >> half_bits, x & half_mask);
+        for round in 0..4 {
+            let f = mix(right ^ mix(key ^ round)) & half_mask;
+            (left, right) = (right, left ^ f);
+        }
+        x = (left << half_bits) | right;
+        if x < len as u64 {
+            return x as u32;
+        }
+    }
+}
+
+/// Map a uniform random u64 to a uniform random index in the range [0, len), without bias.
+///
+/// This is a multiply-shift, which rejects and rehashes the few seeds that would bias it.
+fn uniform_index(seed: u64, len: u16) -> u16 {
+    let len = len as u64;
+    let threshold = len.wrapping_neg() % len;
+    let mut seed = seed;
+    loop {
+        let product = seed as u128 * len as u128;
+        if product as u64 >= threshold {
+            return (product >> 64) as u16;
+        }
+        seed = mix(seed.wrapping_add(COLUMN_SALT));
+    }
 }
 
 /// Map a uniform random u64 to a uniform random f64 in the range [lower, upper).
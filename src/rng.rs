@@ -0,0 +1,66 @@
+//! Portable and reproducible random number generators to choose between.
+
+use rand::{Error, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rand_pcg::Pcg64Mcg;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// The random number generator algorithm used to generate the rain.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum RngKind {
+    /// A 128-bit multiplicative PCG. This is the default.
+    PcgFast,
+
+    /// The ChaCha stream cipher with 8 rounds. This is the statistically strongest.
+    ChaCha8,
+
+    /// The xoshiro256++ generator.
+    Xoshiro,
+}
+
+/// A random number generator of any of the supported kinds.
+///
+/// ChaCha buffers several blocks of output, so it's boxed to keep the others small.
+pub(crate) enum RainRng {
+    PcgFast(Pcg64Mcg),
+    ChaCha8(Box<ChaCha8Rng>),
+    Xoshiro(Xoshiro256PlusPlus),
+}
+
+impl RainRng {
+    /// Build a random number generator of the given kind from a seed.
+    pub(crate) fn new(kind: RngKind, seed: u64) -> RainRng {
+        match kind {
+            RngKind::PcgFast => RainRng::PcgFast(Pcg64Mcg::seed_from_u64(seed)),
+            RngKind::ChaCha8 => RainRng::ChaCha8(Box::new(ChaCha8Rng::seed_from_u64(seed))),
+            RngKind::Xoshiro => RainRng::Xoshiro(Xoshiro256PlusPlus::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl RngCore for RainRng {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            RainRng::PcgFast(rng) => rng.next_u64(),
+            RainRng::ChaCha8(rng) => rng.next_u64(),
+            RainRng::Xoshiro(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            RainRng::PcgFast(rng) => rng.fill_bytes(dest),
+            RainRng::ChaCha8(rng) => rng.fill_bytes(dest),
+            RainRng::Xoshiro(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}